@@ -0,0 +1,42 @@
+use std::fs;
+use anyhow::{anyhow, bail, Result};
+use day_02::game::{parse_games, Bag};
+
+/// Lists the games that could have been played with a given bag, their minimum bags, and
+/// the sum of the feasible game ids. Run as
+/// `feasible [--bag red=12,green=13,blue=14 | --config <path>] [path]`.
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let (bag, path) = match &args[..] {
+        [flag, limits, rest @ ..] if flag == "--bag" => (Bag::parse(limits)?, rest),
+        [flag, config_path, rest @ ..] if flag == "--config" => {
+            (Bag::from_config(&fs::read_to_string(config_path)?)?, rest)
+        },
+        rest => (Bag::standard(), rest),
+    };
+    let input = match path {
+        [] => include_str!("../../input1.txt").to_string(),
+        [path] => fs::read_to_string(path)?,
+        _ => bail!("usage: feasible [--bag <color>=<amount>,... | --config <path>] [path]"),
+    };
+
+    let mut result: u32 = 0;
+    for game in parse_games(&input)? {
+        let minimum_bag = game.minimum_bag();
+        let minimum: Vec<String> = minimum_bag.limits
+            .iter()
+            .map(|(color, amount)| format!("{} {}", amount, color))
+            .collect();
+
+        if game.is_feasible(&bag) {
+            result = result.checked_add(game.id).ok_or(anyhow!("the sum of the game ids does not fit in a u32"))?;
+            println!("game {}: feasible, needs {}", game.id, minimum.join(", "));
+        } else {
+            println!("game {}: impossible, needs {}", game.id, minimum.join(", "));
+        }
+    }
+    println!("sum of feasible ids: {}", result);
+
+    Ok(())
+}
\ No newline at end of file
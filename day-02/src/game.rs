@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum GameError {
+    #[error("line {line}: expected `Game <id>: <draws separated by ;>`, found `{text}`")]
+    InvalidLine { line: usize, text: String },
+
+    #[error("line {line}: expected `<amount> <color>`, found `{text}`")]
+    InvalidCubes { line: usize, text: String },
+
+    #[error("line {line}: {color} is shown twice in one draw")]
+    DuplicateColor { line: usize, color: String },
+
+    #[error("invalid bag limit `{0}`, expected `<color>=<amount>`")]
+    InvalidLimit(String),
+
+    #[error("the bag lists {0} more than once")]
+    DuplicateLimit(String),
+}
+
+/// The cubes shown in one handful, by color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draw {
+    pub cubes: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Draw>,
+}
+
+/// How many cubes of each color the bag holds. Colors it does not list have none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    pub limits: BTreeMap<String, u32>,
+}
+
+impl Draw {
+    fn parse(line: usize, draw_text: &str) -> Result<Self, GameError> {
+        let mut cubes = BTreeMap::new();
+
+        for cubes_text in draw_text.split(',') {
+            let invalid = || GameError::InvalidCubes { line, text: cubes_text.trim().to_string() };
+            let (amount_text, color) = cubes_text.trim().split_once(' ').ok_or_else(invalid)?;
+            let amount: u32 = amount_text.parse().map_err(|_| invalid())?;
+            let color = color.trim();
+            if color.is_empty() {
+                return Err(invalid());
+            }
+
+            if cubes.insert(color.to_string(), amount).is_some() {
+                return Err(GameError::DuplicateColor { line, color: color.to_string() });
+            }
+        }
+
+        Ok(Self { cubes })
+    }
+}
+
+impl Game {
+    /// Parses one game; `line` is the 1-based line number used in errors.
+    pub fn parse(line: usize, game_text: &str) -> Result<Self, GameError> {
+        let invalid = || GameError::InvalidLine { line, text: game_text.to_string() };
+
+        let (id_text, rounds_text) = game_text
+            .trim()
+            .strip_prefix("Game ")
+            .and_then(|rest| rest.split_once(':'))
+            .ok_or_else(invalid)?;
+        let id = id_text.trim().parse().map_err(|_| invalid())?;
+
+        let rounds = rounds_text
+            .split(';')
+            .map(|draw_text| Draw::parse(line, draw_text))
+            .collect::<Result<Vec<Draw>, GameError>>()?;
+
+        Ok(Self { id, rounds })
+    }
+
+    /// Whether every draw could have come out of `bag`.
+    pub fn is_feasible(&self, bag: &Bag) -> bool {
+        self.rounds.iter().all(|draw| {
+            draw.cubes
+                .iter()
+                .all(|(color, amount)| amount <= bag.limits.get(color).unwrap_or(&0))
+        })
+    }
+
+    /// The smallest bag every draw could have come out of.
+    pub fn minimum_bag(&self) -> Bag {
+        let mut limits: BTreeMap<String, u32> = BTreeMap::new();
+        for draw in &self.rounds {
+            for (color, amount) in &draw.cubes {
+                let limit = limits.entry(color.clone()).or_insert(0);
+                *limit = (*limit).max(*amount);
+            }
+        }
+        Bag { limits }
+    }
+}
+
+impl Bag {
+    /// The puzzle's bag: 12 red, 13 green and 14 blue cubes.
+    pub fn standard() -> Self {
+        Self {
+            limits: BTreeMap::from([
+                ("red".to_string(), 12),
+                ("green".to_string(), 13),
+                ("blue".to_string(), 14),
+            ]),
+        }
+    }
+
+    /// Parses limits such as `red=12,green=13,blue=14`.
+    pub fn parse(limits_text: &str) -> Result<Self, GameError> {
+        Self::from_limits(limits_text.split(','))
+    }
+
+    /// Parses a config file with one `<color> = <amount>` limit per line. Blank lines and
+    /// lines starting with `#` are ignored.
+    pub fn from_config(config_text: &str) -> Result<Self, GameError> {
+        Self::from_limits(config_text
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#')))
+    }
+
+    fn from_limits<'a>(limit_texts: impl Iterator<Item = &'a str>) -> Result<Self, GameError> {
+        let mut limits = BTreeMap::new();
+
+        for limit_text in limit_texts {
+            let invalid = || GameError::InvalidLimit(limit_text.trim().to_string());
+            let (color, amount_text) = limit_text.split_once('=').ok_or_else(invalid)?;
+            let color = color.trim();
+            let amount: u32 = amount_text.trim().parse().map_err(|_| invalid())?;
+            if color.is_empty() {
+                return Err(invalid());
+            }
+
+            if limits.insert(color.to_string(), amount).is_some() {
+                return Err(GameError::DuplicateLimit(color.to_string()));
+            }
+        }
+
+        Ok(Self { limits })
+    }
+
+    /// The product of the cube counts of every color, or `None` if it overflows a `u64`.
+    pub fn power(&self) -> Option<u64> {
+        self.limits.values().try_fold(1u64, |product, amount| product.checked_mul(*amount as u64))
+    }
+}
+
+pub fn parse_games(input: &str) -> Result<Vec<Game>, GameError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, game_text)| !game_text.trim().is_empty())
+        .map(|(line_index, game_text)| Game::parse(line_index + 1, game_text))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_parse() -> Result<(), GameError> {
+        let game = Game::parse(1, "Game 7: 3 blue, 4 red; 2 teal")?;
+        assert_eq!(game.id, 7);
+        assert_eq!(game.rounds, vec![
+            Draw { cubes: BTreeMap::from([("blue".to_string(), 3), ("red".to_string(), 4)]) },
+            Draw { cubes: BTreeMap::from([("teal".to_string(), 2)]) },
+        ]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 blue, 4 red; 2 teal", "red=4,blue=3,teal=2", true)]
+    #[case("Game 1: 3 blue, 4 red; 2 teal", "red=4,blue=3,teal=1", false)]
+    #[case("Game 1: 3 blue, 4 red; 2 teal", "red=12,green=13,blue=14", false)]
+    fn test_is_feasible(#[case] game_text: &str, #[case] limits: &str, #[case] expected: bool) -> Result<(), GameError> {
+        assert_eq!(Game::parse(1, game_text)?.is_feasible(&Bag::parse(limits)?), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_minimum_bag() -> Result<(), GameError> {
+        let game = Game::parse(1, "Game 1: 3 blue, 4 red; 1 red, 6 blue; 2 teal")?;
+        let bag = game.minimum_bag();
+        assert_eq!(bag, Bag::parse("blue=6,red=4,teal=2")?);
+        assert_eq!(bag.power(), Some(48));
+        assert!(game.is_feasible(&bag));
+        Ok(())
+    }
+
+    #[test]
+    fn test_power_overflow() -> Result<(), GameError> {
+        assert_eq!(Bag::parse("a=70000,b=70000,c=70000")?.power(), Some(70000u64.pow(3)));
+        assert_eq!(Bag::parse("a=70000,b=70000,c=70000,d=70000")?.power(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_config() -> Result<(), GameError> {
+        let config = "\
+# the puzzle's bag
+red = 12
+green = 13
+
+blue = 14";
+        assert_eq!(Bag::from_config(config)?, Bag::standard());
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            parse_games("Game 1: 3 blue\nGame 2: 3 blue, blue"),
+            Err(GameError::InvalidCubes { line: 2, text }) if text == "blue"
+        ));
+        assert!(matches!(
+            parse_games("Game 1: 3 blue, 2 blue"),
+            Err(GameError::DuplicateColor { line: 1, .. })
+        ));
+        assert!(matches!(parse_games("Round 1: 3 blue"), Err(GameError::InvalidLine { line: 1, .. })));
+        assert!(matches!(Bag::parse("red=12,red=1"), Err(GameError::DuplicateLimit(_))));
+        assert!(matches!(Bag::parse("red:12"), Err(GameError::InvalidLimit(_))));
+    }
+}
\ No newline at end of file
@@ -1,50 +1,25 @@
-use std::collections::HashMap;
-use std::str::FromStr;
-use anyhow::Result;
-use regex::Regex;
+use anyhow::{anyhow, Result};
+use crate::game::{parse_games, Bag};
 
-fn is_game_valid(game: &str) -> bool {
-    let color_amounts: HashMap<&str, u32> = HashMap::from([
-        ("red", 12),
-        ("green", 13),
-        ("blue", 14),
-    ]);
+/// Sums the ids of the games that could have been played with `bag`.
+pub fn process_with_bag(input: &str, bag: &Bag) -> Result<String> {
+    let result = parse_games(input)?
+        .iter()
+        .filter(|game| game.is_feasible(bag))
+        .try_fold(0u32, |sum, game| sum.checked_add(game.id))
+        .ok_or(anyhow!("the sum of the game ids does not fit in a u32"))?;
 
-    for round in game.split(";") {
-        for color_info in round.split(",") {
-            let parts: Vec<&str> = color_info.trim().split(" ").collect();
-            let amount: u32 = parts[0].trim().parse().expect("amount is an integer");
-            let color = parts[1].trim();
-
-            if &amount > color_amounts.get(color).expect("color is one of red, green, or blue") {
-                return false;
-            }
-        }
-    }
-
-    true
+    Ok(result.to_string())
 }
 
 pub fn process(input: &str) -> Result<String> {
-    let re = Regex::new(r"Game (\d+): (.+)")?;
-
-    let mut result = 0;
-    for line in input.lines() {
-        let caps = re.captures(line).expect("line matches the expected format");
-        let id: u32 = caps[1].parse().expect("id is an integer");
-        let game = &caps[2];
-
-        if is_game_valid(game) {
-            result += id;
-        }
-    }
-
-    Ok(result.to_string())
+    process_with_bag(input, &Bag::standard())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::Game;
     use rstest::rstest;
 
     #[test]
@@ -60,13 +35,22 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
         Ok(())
     }
 
+    #[test]
+    fn test_process_overflow() {
+        let input = format!("Game {}: 1 red\nGame 1: 1 red", u32::MAX);
+        let error = process(&input).unwrap_err();
+        assert_eq!(error.to_string(), "the sum of the game ids does not fit in a u32");
+    }
+
     #[rstest]
     #[case("3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", true)]
     #[case("1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue", true)]
     #[case("8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red", false)]
     #[case("1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red", false)]
     #[case("6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", true)]
-    fn test_is_game_valid(#[case] input: &str, #[case] expected: bool) {
-        assert_eq!(is_game_valid(input), expected);
+    fn test_is_game_valid(#[case] input: &str, #[case] expected: bool) -> Result<()> {
+        let game = Game::parse(1, &format!("Game 1: {}", input))?;
+        assert_eq!(game.is_feasible(&Bag::standard()), expected);
+        Ok(())
     }
 }
\ No newline at end of file
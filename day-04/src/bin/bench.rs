@@ -0,0 +1,30 @@
+use std::time::Instant;
+use anyhow::{ensure, Result};
+use day_04::fast::{generate_cards, CardMatches};
+use day_04::scratchcard::parse_cards;
+
+/// Times counting matches with typed `Scratchcard`s against the allocation-free bitmask
+/// reader on generated cards. Run as `cargo run --release --bin bench [num_cards]`.
+fn main() -> Result<()> {
+    let num_cards = match std::env::args().nth(1) {
+        Some(num_cards) => num_cards.parse()?,
+        None => 1_000_000,
+    };
+    let input = generate_cards(num_cards, 2023);
+
+    let start = Instant::now();
+    let typed_total: u64 = parse_cards(&input)?.iter().map(|card| card.matches() as u64).sum();
+    let typed_time = start.elapsed();
+
+    let start = Instant::now();
+    let mut fast_total: u64 = 0;
+    for num_matches in CardMatches::new(&input) {
+        fast_total += num_matches? as u64;
+    }
+    let fast_time = start.elapsed();
+
+    ensure!(typed_total == fast_total, "match totals differ ({} vs {})", typed_total, fast_total);
+    println!("{} cards: Scratchcard {:?}, bitmask reader {:?}", num_cards, typed_time, fast_time);
+
+    Ok(())
+}
\ No newline at end of file
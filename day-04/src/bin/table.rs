@@ -0,0 +1,49 @@
+use std::fs;
+use anyhow::{anyhow, bail, Result};
+use day_04::copies::{copy_table, CopyRules, Growth, PastEnd};
+use day_04::fast::CardMatches;
+
+/// Prints every card's matches, points, wins past the last card and final copy count.
+/// Run as `table [--cap <n>] [--wrap] [--double] [path]` to change how copies are won.
+fn main() -> Result<()> {
+    let mut rules = CopyRules::standard();
+    let mut path = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cap" => {
+                let cap = args.next().ok_or(anyhow!("--cap needs a number of copies"))?;
+                rules = rules.with_cap(cap.parse()?);
+            },
+            "--wrap" => rules = rules.with_past_end(PastEnd::Wrap),
+            "--double" => rules = rules.with_growth(Growth::Double),
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => bail!("usage: table [--cap <n>] [--wrap] [--double] [path]"),
+        }
+    }
+
+    let input = match path {
+        Some(path) => fs::read_to_string(path)?,
+        None => include_str!("../../input2.txt").to_string(),
+    };
+    let card_values: Vec<u32> = CardMatches::new(&input).collect::<Result<_, _>>()?;
+    let table = copy_table(&card_values, &rules)?;
+
+    println!("{:>6} {:>7} {:>6} {:>8} {:>20}", "card", "matches", "points", "past end", "copies");
+    for row in &table {
+        println!("{}", row);
+    }
+
+    let total_points = table
+        .iter()
+        .try_fold(0u64, |total, row| row.points().and_then(|points| total.checked_add(points)))
+        .ok_or(anyhow!("the points do not fit in a u64"))?;
+    let total_copies = table
+        .iter()
+        .try_fold(0u64, |total, row| total.checked_add(row.copies))
+        .ok_or(anyhow!("the number of cards does not fit in a u64"))?;
+    println!("total points: {}, total cards: {}", total_points, total_copies);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,256 @@
+use std::fmt;
+use thiserror::Error;
+use crate::scratchcard::points;
+
+#[derive(Error, Debug)]
+pub enum CopyError {
+    #[error("the copies of card {id} do not fit in a u64")]
+    Overflow { id: usize },
+
+    #[error("card {id} keeps winning copies of itself through wrapping, so the copies never settle; set a cap")]
+    EndlessCopies { id: usize },
+}
+
+/// What happens to wins that would land past the last card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PastEnd {
+    Truncate,
+    Wrap,
+}
+
+/// How a won copy changes the count of the card it lands on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Growth {
+    /// Each copy played adds one copy.
+    Add,
+    /// Each copy played doubles the count.
+    Double,
+}
+
+/// The rules for winning copies. The puzzle's rules add copies, never win past the last
+/// card and have no cap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyRules {
+    cap: Option<u64>,
+    past_end: PastEnd,
+    growth: Growth,
+}
+
+impl CopyRules {
+    pub fn standard() -> Self {
+        Self { cap: None, past_end: PastEnd::Truncate, growth: Growth::Add }
+    }
+
+    /// Limits every card to `cap` copies, counting the original; wins beyond that are lost.
+    pub fn with_cap(mut self, cap: u64) -> Self {
+        self.cap = Some(cap.max(1));
+        self
+    }
+
+    pub fn with_past_end(mut self, past_end: PastEnd) -> Self {
+        self.past_end = past_end;
+        self
+    }
+
+    pub fn with_growth(mut self, growth: Growth) -> Self {
+        self.growth = growth;
+        self
+    }
+
+    /// Indices of the cards won by card `index`.
+    fn won_cards(&self, index: usize, num_matches: u32, num_cards: usize) -> impl Iterator<Item = usize> {
+        let past_end = self.past_end;
+        (index + 1..=index + num_matches as usize)
+            .filter_map(move |won| match past_end {
+                PastEnd::Truncate => (won < num_cards).then_some(won),
+                PastEnd::Wrap => Some(won % num_cards),
+            })
+    }
+
+    /// The count after `played` copies of a winning card land on a card holding `copies`.
+    fn grow(&self, copies: u64, played: u64) -> Option<u64> {
+        let grown = match self.growth {
+            Growth::Add => copies.checked_add(played),
+            Growth::Double => u32::try_from(played)
+                .ok()
+                .and_then(|played| 2u64.checked_pow(played))
+                .and_then(|factor| copies.checked_mul(factor)),
+        };
+
+        match self.cap {
+            Some(cap) => Some(grown.map_or(cap, |grown| grown.min(cap))),
+            None => grown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardRow {
+    pub id: usize,
+    pub matches: u32,
+    /// Wins that land past the last card, which the puzzle promises never happen.
+    pub wins_past_end: u32,
+    pub copies: u64,
+}
+
+impl CardRow {
+    /// Only worked out when asked for, since cards with more than 64 matches have more
+    /// points than fit in a `u64` but still win copies as usual.
+    pub fn points(&self) -> Option<u64> {
+        points(self.matches)
+    }
+}
+
+impl fmt::Display for CardRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let points = self.points().map_or("-".to_string(), |points| points.to_string());
+        write!(f, "{:>6} {:>7} {:>6} {:>8} {:>20}", self.id, self.matches, points, self.wins_past_end, self.copies)
+    }
+}
+
+/// Whether some card wins copies of itself, directly or through other cards, which with
+/// wrapping and no cap would go on forever.
+fn find_win_cycle(matches: &[u32], rules: &CopyRules) -> Option<usize> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Visit {
+        New,
+        Open,
+        Done,
+    }
+
+    let mut visits = vec![Visit::New; matches.len()];
+    for root in 0..matches.len() {
+        if visits[root] != Visit::New {
+            continue;
+        }
+
+        // iterative depth-first search, keeping each open card's remaining wins
+        visits[root] = Visit::Open;
+        let mut stack = vec![(root, rules.won_cards(root, matches[root], matches.len()))];
+        while let Some((card, won_cards)) = stack.last_mut() {
+            match won_cards.next() {
+                Some(won) if visits[won] == Visit::Open => return Some(won),
+                Some(won) if visits[won] == Visit::New => {
+                    visits[won] = Visit::Open;
+                    stack.push((won, rules.won_cards(won, matches[won], matches.len())));
+                },
+                Some(_) => {},
+                None => {
+                    visits[*card] = Visit::Done;
+                    stack.pop();
+                },
+            }
+        }
+    }
+    None
+}
+
+/// Works out every card's final copy count under `rules`. Copies still to be played are
+/// kept per card and played in card order, a whole batch at a time; with the standard rules
+/// wins only flow forward, so one pass settles everything. Wrapping can send wins back to
+/// cards already played, so passes repeat until no copies are left to play.
+pub fn copy_table(matches: &[u32], rules: &CopyRules) -> Result<Vec<CardRow>, CopyError> {
+    if rules.past_end == PastEnd::Wrap && rules.cap.is_none() {
+        if let Some(index) = find_win_cycle(matches, rules) {
+            return Err(CopyError::EndlessCopies { id: index + 1 });
+        }
+    }
+
+    let mut copies = vec![1u64; matches.len()];
+    let mut to_play = copies.clone();
+
+    let mut played_any = true;
+    while played_any {
+        played_any = false;
+        for (index, num_matches) in matches.iter().enumerate() {
+            let played = std::mem::take(&mut to_play[index]);
+            if played == 0 {
+                continue;
+            }
+            played_any = true;
+
+            for won in rules.won_cards(index, *num_matches, matches.len()) {
+                let grown = rules
+                    .grow(copies[won], played)
+                    .ok_or(CopyError::Overflow { id: won + 1 })?;
+                to_play[won] += grown - copies[won];
+                copies[won] = grown;
+            }
+        }
+    }
+
+    let num_cards = matches.len();
+    Ok(matches
+        .iter()
+        .zip(copies)
+        .enumerate()
+        .map(|(index, (&matches, copies))| CardRow {
+            id: index + 1,
+            matches,
+            wins_past_end: matches.saturating_sub((num_cards - index - 1) as u32),
+            copies,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // match counts of the puzzle's example cards
+    const EXAMPLE: [u32; 6] = [4, 2, 2, 1, 0, 0];
+
+    fn copies(matches: &[u32], rules: &CopyRules) -> Result<Vec<u64>, CopyError> {
+        Ok(copy_table(matches, rules)?.iter().map(|row| row.copies).collect())
+    }
+
+    #[rstest]
+    #[case(CopyRules::standard(), [1, 2, 4, 8, 14, 1])]
+    #[case(CopyRules::standard().with_cap(5), [1, 2, 4, 5, 5, 1])]
+    #[case(CopyRules::standard().with_growth(Growth::Double).with_cap(100), [1, 2, 8, 100, 100, 1])]
+    fn test_copy_table(#[case] rules: CopyRules, #[case] expected: [u64; 6]) -> Result<(), CopyError> {
+        assert_eq!(copies(&EXAMPLE, &rules)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_double() -> Result<(), CopyError> {
+        let double = CopyRules::standard().with_growth(Growth::Double);
+
+        // both copies of card 2 double card 3
+        assert_eq!(copies(&[1, 1, 0], &double)?, [1, 2, 4]);
+        assert!(matches!(copy_table(&EXAMPLE, &double), Err(CopyError::Overflow { id: 5 })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrap() -> Result<(), CopyError> {
+        let wrap = CopyRules::standard().with_past_end(PastEnd::Wrap);
+
+        // card 3 wins another card 1, which then wins another card 2
+        assert_eq!(copies(&[1, 0, 1], &wrap)?, [2, 3, 1]);
+        assert_eq!(copies(&[1, 0, 1], &CopyRules::standard())?, [1, 2, 1]);
+
+        // nothing reaches past the end, so wrapping changes nothing
+        assert_eq!(copies(&EXAMPLE, &wrap)?, copies(&EXAMPLE, &CopyRules::standard())?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_endless_wrap() -> Result<(), CopyError> {
+        let wrap = CopyRules::standard().with_past_end(PastEnd::Wrap);
+        assert!(matches!(copy_table(&[1, 1, 1], &wrap), Err(CopyError::EndlessCopies { .. })));
+        assert_eq!(copies(&[1, 1, 1], &wrap.with_cap(10))?, [10, 10, 10]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_display() -> Result<(), CopyError> {
+        let table = copy_table(&EXAMPLE, &CopyRules::standard())?;
+        assert_eq!(table[1].to_string(), "     2       2      2        0                    2");
+        assert!(table.iter().all(|row| row.wins_past_end == 0));
+        assert_eq!(copy_table(&[0, 3], &CopyRules::standard())?[1].wins_past_end, 3);
+        Ok(())
+    }
+}
\ No newline at end of file
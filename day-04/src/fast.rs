@@ -0,0 +1,180 @@
+use std::iter::Enumerate;
+use std::str::Lines;
+use crate::scratchcard::{CardError, Scratchcard};
+
+/// Reads a run of digits starting at `pos`, returning the number and the position after it.
+fn read_number(bytes: &[u8], mut pos: usize) -> Option<(u32, usize)> {
+    let start = pos;
+    let mut number: u32 = 0;
+    while let Some(digit) = bytes.get(pos).filter(|byte| byte.is_ascii_digit()) {
+        number = number.checked_mul(10)?.checked_add((digit - b'0') as u32)?;
+        pos += 1;
+    }
+    (pos > start).then_some((number, pos))
+}
+
+fn skip_spaces(bytes: &[u8], mut pos: usize) -> usize {
+    while bytes.get(pos) == Some(&b' ') {
+        pos += 1;
+    }
+    pos
+}
+
+/// Reads numbers separated by spaces into a mask until `end` (or the end of the line).
+fn read_mask(bytes: &[u8], mut pos: usize, end: Option<u8>) -> Option<(u128, usize)> {
+    let mut mask = 0u128;
+    loop {
+        pos = skip_spaces(bytes, pos);
+        match bytes.get(pos) {
+            None if end.is_none() => return Some((mask, pos)),
+            Some(byte) if Some(*byte) == end => return Some((mask, pos + 1)),
+            _ => {},
+        }
+
+        let (number, next_pos) = read_number(bytes, pos)?;
+        if number >= 128 || mask >> number & 1 == 1 {
+            return None;
+        }
+        mask |= 1 << number;
+        pos = next_pos;
+    }
+}
+
+/// Reads a card's id and match count straight from the line's bytes, without allocating.
+/// Returns `None` for anything it does not handle (numbers of 128 or more, repeated numbers,
+/// malformed lines) so the caller can fall back to `Scratchcard::parse`, which either copes
+/// or reports the problem.
+pub fn read_card(card_text: &str) -> Option<(u32, u32)> {
+    let bytes = card_text.trim().as_bytes().strip_prefix(b"Card")?;
+
+    let (id, pos) = read_number(bytes, skip_spaces(bytes, 0))?;
+    let pos = skip_spaces(bytes, pos);
+    if bytes.get(pos) != Some(&b':') {
+        return None;
+    }
+
+    let (winning, pos) = read_mask(bytes, pos + 1, Some(b'|'))?;
+    let (have, _) = read_mask(bytes, pos, None)?;
+
+    Some((id, (winning & have).count_ones()))
+}
+
+/// The match count of every card in order, with the same id checks as `parse_cards`.
+pub struct CardMatches<'a> {
+    lines: Enumerate<Lines<'a>>,
+    next_id: u32,
+}
+
+impl<'a> CardMatches<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { lines: input.lines().enumerate(), next_id: 1 }
+    }
+}
+
+impl Iterator for CardMatches<'_> {
+    type Item = Result<u32, CardError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (line_index, card_text) = self.lines.find(|(_, card_text)| !card_text.trim().is_empty())?;
+        let line = line_index + 1;
+
+        let (id, matches) = match read_card(card_text) {
+            Some(card) => card,
+            None => match Scratchcard::parse(line, card_text) {
+                Ok(card) => (card.id, card.matches()),
+                Err(error) => return Some(Err(error)),
+            },
+        };
+
+        let expected = self.next_id;
+        // ids so far ran 1, 2, ... without gaps, so only those can be repeats
+        if (1..expected).contains(&id) {
+            return Some(Err(CardError::DuplicateId { line, id }));
+        }
+        if id != expected {
+            return Some(Err(CardError::UnexpectedId { line, expected, actual: id }));
+        }
+        self.next_id += 1;
+
+        Some(Ok(matches))
+    }
+}
+
+/// Writes `num_cards` cards with 5 winning numbers and 8 numbers each, so that card `i`
+/// never has more matches than there are cards after it. Each card's choices are read off
+/// the bits of one multiplicative hash of its id and `seed`.
+pub fn generate_cards(num_cards: usize, seed: u64) -> String {
+    let mut cards = String::with_capacity(num_cards * 48);
+    for card_id in 1..=num_cards {
+        let bits = (card_id as u64 ^ seed.rotate_left(32)).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+
+        let max_matches = (num_cards - card_id).min(5) as u64;
+        let num_matches = ((bits >> 56) % (max_matches + 1)) as usize;
+
+        // winning numbers come from 10..60 and the rest from 60..100, so only the copied
+        // numbers match
+        let winning: Vec<u64> = (0..5).map(|i| 10 + i * 10 + (bits >> (4 * i)) % 10).collect();
+        let have: Vec<u64> = (0..8)
+            .map(|i| if i < num_matches { winning[i] } else { 60 + i as u64 * 5 + (bits >> (20 + 4 * i)) % 5 })
+            .collect();
+
+        let join = |nums: &[u64]| nums.iter().map(|num| format!("{:2}", num)).collect::<Vec<_>>().join(" ");
+        cards.push_str(&format!("Card {:3}: {} | {}\n", card_id, join(&winning), join(&have)));
+    }
+    cards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratchcard::parse_cards;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", Some((1, 4)))]
+    #[case("Card  12:  1 21 53 59 44 | 69 82 63 72 16 21 14  1", Some((12, 2)))]
+    #[case("Card 5: 87 | ", Some((5, 0)))]
+    #[case("Card 1: 41 128 | 41", None)]
+    #[case("Card 1: 41 41 | 41", None)]
+    #[case("Card 1: 41 48", None)]
+    #[case("Game 1: 41 | 48", None)]
+    fn test_read_card(#[case] card_text: &str, #[case] expected: Option<(u32, u32)>) {
+        assert_eq!(read_card(card_text), expected);
+    }
+
+    #[test]
+    fn test_matches_scratchcards() -> Result<(), CardError> {
+        let mut input = generate_cards(5000, 99);
+        input.push_str("Card 5001: 200 7 300 | 300 7 8 200\n");
+
+        let fast: Vec<u32> = CardMatches::new(&input).collect::<Result<_, _>>()?;
+        let typed: Vec<u32> = parse_cards(&input)?.iter().map(|card| card.matches()).collect();
+        assert_eq!(fast, typed);
+        assert_eq!(fast.last(), Some(&3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors_match_scratchcards() {
+        let input = "Card 1: 1 2 | 3\nCard 2: 4 4 | 5";
+        assert!(matches!(
+            CardMatches::new(input).collect::<Result<Vec<u32>, _>>(),
+            Err(CardError::DuplicateNumber { line: 2, list: "winning", number: 4 })
+        ));
+        assert!(matches!(
+            CardMatches::new("Card 1: 1 | 2\nCard 1: 1 | 2").collect::<Result<Vec<u32>, _>>(),
+            Err(CardError::DuplicateId { line: 2, id: 1 })
+        ));
+    }
+
+    #[rstest]
+    #[case("Card 0: 1 | 2")]
+    #[case("Card 1: 1 | 2\nCard 0: 1 | 2")]
+    #[case("Card 1: 1 | 2\nCard 1: 1 | 2")]
+    #[case("Card 1: 1 | 2\nCard 3: 1 | 2")]
+    fn test_id_errors_match_scratchcards(#[case] input: &str) {
+        let fast_error = CardMatches::new(input).collect::<Result<Vec<u32>, _>>().unwrap_err();
+        let typed_error = parse_cards(input).unwrap_err();
+        assert_eq!(fast_error.to_string(), typed_error.to_string());
+    }
+}
\ No newline at end of file
@@ -1,2 +1,5 @@
+pub mod copies;
+pub mod fast;
 pub mod part1;
-pub mod part2;
\ No newline at end of file
+pub mod part2;
+pub mod scratchcard;
\ No newline at end of file
@@ -1,47 +1,14 @@
-use std::collections::HashSet;
-use anyhow::Result;
-use regex::{Regex, Captures};
-
-const CARD_RE: &'static str = r"Card +\d+: ([\d ]+) +\| +([\d ]+)";
-
-fn card_value(cap: Captures) -> u32 {
-    assert_eq!(cap.len(), 3);
-
-    let winning_numbers: HashSet<u32> = cap
-        .get(1).expect("card text contains winning numbers")
-        .as_str()
-        .trim()
-        .split(' ')
-        .filter(|num_text| num_text.trim().len() > 0)
-        .map(|num_text| num_text.parse::<u32>().expect("winning number is an integer"))
-        .collect();
-
-    let my_numbers = cap
-        .get(2).expect("card text contains my numbers")
-        .as_str()
-        .trim()
-        .split(' ')
-        .filter(|num_text| num_text.trim().len() > 0)
-        .map(|num_text| num_text.parse::<u32>().expect("my number is an integer"));
-
-    let num_winners = my_numbers
-        .filter(|num| winning_numbers.contains(num))
-        .count() as u32;
-
-    if num_winners == 0 {
-        return 0;
-    }
-
-    2u32.pow(num_winners - 1)
-}
+use anyhow::{anyhow, Result};
+use crate::fast::CardMatches;
+use crate::scratchcard::points;
 
 pub fn process(input: &str) -> Result<String> {
-    let card_re = Regex::new(CARD_RE)?;
-
-    let result = card_re
-        .captures_iter(input)
-        .map(card_value)
-        .fold(0, |acc, elem| acc + elem);
+    let mut result: u64 = 0;
+    for num_matches in CardMatches::new(input) {
+        result = points(num_matches?)
+            .and_then(|card_points| result.checked_add(card_points))
+            .ok_or(anyhow!("the points do not fit in a u64"))?;
+    }
 
     Ok(result.to_string())
 }
@@ -49,6 +16,7 @@ pub fn process(input: &str) -> Result<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scratchcard::Scratchcard;
     use rstest::rstest;
 
     #[test]
@@ -72,9 +40,8 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
     #[case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", 0)]
     #[case("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11", 0)]
     #[case("Card   1: 61 73 92 28 96 76 32 62 44 53 | 61 17 26 13 92  5 73 29 53 42 62 46 96 32 21 97 99 28 12  4  7 44 19 71 76", 512)]
-    fn test_card_value(#[case] card_text: &str, #[case] expected: u32) {
-        let card_re = Regex::new(CARD_RE).expect("regex is valid");
-        let cap = card_re.captures(card_text).expect("there is a capture");
-        assert_eq!(card_value(cap), expected);
+    fn test_card_value(#[case] card_text: &str, #[case] expected: u64) -> Result<()> {
+        assert_eq!(Scratchcard::parse(1, card_text)?.points(), Some(expected));
+        Ok(())
     }
 }
\ No newline at end of file
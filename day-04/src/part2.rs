@@ -1,68 +1,72 @@
-use std::collections::{HashSet, VecDeque};
-use anyhow::Result;
-use regex::{Regex, Captures};
+use anyhow::{anyhow, Result};
+use crate::copies::{copy_table, CopyRules};
+use crate::fast::CardMatches;
 
-const CARD_RE: &'static str = r"Card +\d+: ([\d ]+) +\| +([\d ]+)";
-
-fn card_value(cap: Captures) -> u32 {
-    assert_eq!(cap.len(), 3);
-
-    let winning_numbers: HashSet<u32> = cap
-        .get(1).expect("card text contains winning numbers")
-        .as_str()
-        .trim()
-        .split(' ')
-        .filter(|num_text| num_text.trim().len() > 0)
-        .map(|num_text| num_text.parse::<u32>().expect("winning number is an integer"))
-        .collect();
+pub fn process(input: &str) -> Result<String> {
+    let card_values: Vec<u32> = CardMatches::new(input).collect::<Result<_, _>>()?;
 
-    let my_numbers = cap
-        .get(2).expect("card text contains my numbers")
-        .as_str()
-        .trim()
-        .split(' ')
-        .filter(|num_text| num_text.trim().len() > 0)
-        .map(|num_text| num_text.parse::<u32>().expect("my number is an integer"));
+    let result = copy_table(&card_values, &CopyRules::standard())?
+        .iter()
+        .try_fold(0u64, |total, row| total.checked_add(row.copies))
+        .ok_or(anyhow!("the number of cards does not fit in a u64"))?;
 
-    my_numbers
-        .filter(|num| winning_numbers.contains(num))
-        .count() as u32
+    Ok(result.to_string())
 }
 
-pub fn process(input: &str) -> Result<String> {
-    let card_re = Regex::new(CARD_RE)?;
-
-    let card_values: Vec<u32> = card_re
-        .captures_iter(input)
-        .map(card_value)
-        .collect();
-
-    let mut worklist: VecDeque<(usize, u32)> = VecDeque::with_capacity(card_values.len());
-    worklist.extend(card_values
-        .iter()
-        .enumerate()
-        .map(|(i, val)| (i, *val)));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use crate::fast::generate_cards;
+    use crate::scratchcard::{parse_cards, Scratchcard};
+    use rstest::rstest;
 
-    let mut result = 0;
-    while let Some((index, value)) = worklist.pop_front() {
-        result += 1;
-        // NOTE: this is fairly inefficient, but I didn't want to spend too long figuring out
-        // how to subslice the values with indices
+    /// The original implementation, which queues every won copy individually.
+    fn count_copies_worklist(card_values: &[u32]) -> u64 {
+        let mut worklist: VecDeque<(usize, u32)> = VecDeque::with_capacity(card_values.len());
         worklist.extend(card_values
             .iter()
             .enumerate()
-            .skip(index + 1)
-            .take(value as usize)
             .map(|(i, val)| (i, *val)));
+
+        let mut result = 0;
+        while let Some((index, value)) = worklist.pop_front() {
+            result += 1;
+            worklist.extend(card_values
+                .iter()
+                .enumerate()
+                .skip(index + 1)
+                .take(value as usize)
+                .map(|(i, val)| (i, *val)));
+        }
+        result
     }
 
-    Ok(result.to_string())
-}
+    #[test]
+    fn test_process_many_matches() -> Result<()> {
+        let numbers: Vec<String> = (1..=40).map(|num| num.to_string()).collect();
+        let mut input = format!("Card 1: {} | {}\n", numbers.join(" "), numbers.join(" "));
+        for card_id in 2..=41 {
+            input.push_str(&format!("Card {}: 1 | 2\n", card_id));
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rstest::rstest;
+        // card 1 wins one copy of each of the 40 cards after it
+        assert_eq!(process(&input)?, "81");
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    #[case(4)]
+    fn test_matches_worklist(#[case] seed: u64) -> Result<()> {
+        let input = generate_cards(18, seed);
+        let card_values: Vec<u32> = parse_cards(&input)?.iter().map(|card| card.matches()).collect();
+
+        assert_eq!(process(&input)?, count_copies_worklist(&card_values).to_string());
+        Ok(())
+    }
 
     #[test]
     fn test_process() -> Result<()> {
@@ -84,9 +88,8 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
     #[case("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83", 1)]
     #[case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", 0)]
     #[case("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11", 0)]
-    fn test_card_value(#[case] card_text: &str, #[case] expected: u32) {
-        let card_re = Regex::new(CARD_RE).expect("regex is valid");
-        let cap = card_re.captures(card_text).expect("there is a capture");
-        assert_eq!(card_value(cap), expected);
+    fn test_card_value(#[case] card_text: &str, #[case] expected: u32) -> Result<()> {
+        assert_eq!(Scratchcard::parse(1, card_text)?.matches(), expected);
+        Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,166 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CardError {
+    #[error("line {line}: expected `Card <id>: <winning numbers> | <numbers you have>`, found `{text}`")]
+    InvalidLine { line: usize, text: String },
+
+    #[error("line {line}: `{text}` is not a number")]
+    InvalidNumber { line: usize, text: String },
+
+    #[error("line {line}: card {id} was already listed")]
+    DuplicateId { line: usize, id: u32 },
+
+    #[error("line {line}: expected card {expected}, found card {actual}")]
+    UnexpectedId { line: usize, expected: u32, actual: u32 },
+
+    #[error("line {line}: {number} appears more than once in the {list} numbers")]
+    DuplicateNumber { line: usize, list: &'static str, number: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcard {
+    pub id: u32,
+    pub winning: Vec<u32>,
+    pub have: Vec<u32>,
+}
+
+fn parse_numbers(line: usize, list: &'static str, numbers_text: &str) -> Result<Vec<u32>, CardError> {
+    let mut numbers: Vec<u32> = Vec::new();
+    for num_text in numbers_text.split_whitespace() {
+        let number = num_text
+            .parse()
+            .map_err(|_| CardError::InvalidNumber { line, text: num_text.to_string() })?;
+        if numbers.contains(&number) {
+            return Err(CardError::DuplicateNumber { line, list, number });
+        }
+        numbers.push(number);
+    }
+    Ok(numbers)
+}
+
+/// One point for the first match, doubled for each match after it, or `None` past 64
+/// matches where the points no longer fit in a `u64`.
+pub fn points(num_matches: u32) -> Option<u64> {
+    match num_matches {
+        0 => Some(0),
+        num_matches => 1u64.checked_shl(num_matches - 1),
+    }
+}
+
+/// Sets bit `n` for every number `n`, if they all fit in 128 bits.
+pub fn number_mask(numbers: &[u32]) -> Option<u128> {
+    numbers
+        .iter()
+        .try_fold(0u128, |mask, &number| (number < 128).then(|| mask | 1 << number))
+}
+
+impl Scratchcard {
+    /// Parses one card; `line` is the 1-based line number used in errors.
+    pub fn parse(line: usize, card_text: &str) -> Result<Self, CardError> {
+        let invalid = || CardError::InvalidLine { line, text: card_text.to_string() };
+
+        let (id_text, numbers_text) = card_text
+            .trim()
+            .strip_prefix("Card")
+            .and_then(|rest| rest.split_once(':'))
+            .ok_or_else(invalid)?;
+        let (winning_text, have_text) = numbers_text.split_once('|').ok_or_else(invalid)?;
+
+        let id = id_text
+            .trim()
+            .parse()
+            .map_err(|_| CardError::InvalidNumber { line, text: id_text.trim().to_string() })?;
+
+        Ok(Self {
+            id,
+            winning: parse_numbers(line, "winning", winning_text)?,
+            have: parse_numbers(line, "have", have_text)?,
+        })
+    }
+
+    /// Intersects bitmasks when every number is below 128, which covers real inputs, and
+    /// falls back to comparing the lists otherwise.
+    pub fn matches(&self) -> u32 {
+        if let (Some(winning), Some(have)) = (number_mask(&self.winning), number_mask(&self.have)) {
+            return (winning & have).count_ones();
+        }
+
+        self.have
+            .iter()
+            .filter(|num| self.winning.contains(num))
+            .count() as u32
+    }
+
+    pub fn points(&self) -> Option<u64> {
+        points(self.matches())
+    }
+}
+
+/// Parses every card, checking that ids count up from 1 with no repeats, since part 2 wins
+/// copies of "the next N cards" by id.
+pub fn parse_cards(input: &str) -> Result<Vec<Scratchcard>, CardError> {
+    let mut cards: Vec<Scratchcard> = Vec::new();
+
+    for (line_index, card_text) in input.lines().enumerate() {
+        if card_text.trim().is_empty() {
+            continue;
+        }
+
+        let card = Scratchcard::parse(line_index + 1, card_text)?;
+        let expected = cards.len() as u32 + 1;
+        if card.id != expected {
+            if cards.iter().any(|other| other.id == card.id) {
+                return Err(CardError::DuplicateId { line: line_index + 1, id: card.id });
+            }
+            return Err(CardError::UnexpectedId { line: line_index + 1, expected, actual: card.id });
+        }
+        cards.push(card);
+    }
+
+    Ok(cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), CardError> {
+        let card = Scratchcard::parse(1, "Card   3:  1 21 53 | 69  1 21 14")?;
+        assert_eq!(card, Scratchcard { id: 3, winning: vec![1, 21, 53], have: vec![69, 1, 21, 14] });
+        assert_eq!((card.matches(), card.points()), (2, Some(2)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_past_mask() -> Result<(), CardError> {
+        let card = Scratchcard::parse(1, "Card 1: 5 127 128 900 | 900 127 3 128")?;
+        assert_eq!(number_mask(&card.winning), None);
+        assert_eq!(card.matches(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_cards("Card 1: 1 2 | 3\nCard 3: 4 | 5").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected card 2, found card 3");
+
+        assert!(matches!(
+            parse_cards("Card 1: 1 2 | 3\nCard 1: 4 | 5"),
+            Err(CardError::DuplicateId { line: 2, id: 1 })
+        ));
+        assert!(matches!(
+            parse_cards("Card 1: 1 2 | 3 9 3"),
+            Err(CardError::DuplicateNumber { line: 1, list: "have", number: 3 })
+        ));
+        assert!(matches!(
+            parse_cards("Card 1: 1 x | 3"),
+            Err(CardError::InvalidNumber { line: 1, text }) if text == "x"
+        ));
+        assert!(matches!(
+            parse_cards("Card 1: 1 2 3"),
+            Err(CardError::InvalidLine { line: 1, .. })
+        ));
+    }
+}
\ No newline at end of file
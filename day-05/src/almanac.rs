@@ -0,0 +1,751 @@
+use std::cmp::{max, min};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::ops::Range;
+use itertools::Itertools;
+use rangemap::RangeSet;
+use regex::Regex;
+use thiserror::Error;
+use crate::piecewise::PiecewiseMap;
+
+#[derive(Error, Debug)]
+pub enum AlmanacError {
+    #[error("the almanac does not start with a `seeds:` line")]
+    MissingSeeds,
+
+    #[error("invalid seed value: `{0}`")]
+    InvalidSeed(String),
+
+    #[error("seed ranges come in `start length` pairs, but `{0}` has no length")]
+    UnpairedSeed(u64),
+
+    #[error("the seed range starting at {start} with length {length} runs past the largest u64")]
+    SeedRangeOverflow { start: u64, length: u64 },
+
+    #[error("found a map row before any `X-to-Y map:` header: `{0}`")]
+    MissingHeader(String),
+
+    #[error("invalid row on line {line} in the `{section}` map: `{row}`")]
+    InvalidRow { section: String, line: usize, row: String },
+
+    #[error("the `{0}` map appears more than once")]
+    DuplicateMap(String),
+
+    #[error("unknown category `{0}`")]
+    UnknownCategory(String),
+
+    #[error("no chain of maps leads from `{from}` to `{to}`: it stops at `{stopped_at}`")]
+    BrokenChain { from: String, to: String, stopped_at: String },
+
+    #[error("the almanac is inconsistent:\n{}", .0.iter().map(|issue| format!("  {}", issue)).join("\n"))]
+    Inconsistent(Vec<AlmanacIssue>),
+
+    #[error("invalid regex")]
+    RegexError(#[from] regex::Error),
+}
+
+/// A problem with a map row that parses fine but makes the almanac ambiguous or unusable.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AlmanacIssue {
+    #[error("line {line} in the `{section}` map has a zero-length range")]
+    ZeroLength { section: String, line: usize },
+
+    #[error("line {line} in the `{section}` map runs past the largest u64")]
+    Overflow { section: String, line: usize },
+
+    #[error("line {line} in the `{section}` map overlaps the source range on line {other_line}")]
+    Overlap { section: String, line: usize, other_line: usize },
+}
+
+/// A single `dest_start source_start range_len` row of a map section, along with the
+/// (1-based) input line it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapRow {
+    pub dest_start: u64,
+    pub source_start: u64,
+    pub range_len: u64,
+    pub line: usize,
+}
+
+impl MapRow {
+    fn overflows(&self) -> bool {
+        self.source_start.checked_add(self.range_len).is_none()
+            || self.dest_start.checked_add(self.range_len).is_none()
+    }
+
+    pub fn source_range(&self) -> Range<u64> {
+        self.source_start..self.source_start + self.range_len
+    }
+
+    pub fn dest_range(&self) -> Range<u64> {
+        self.dest_start..self.dest_start + self.range_len
+    }
+
+    fn dest_of(&self, source_num: u64) -> u64 {
+        self.dest_start + (source_num - self.source_start)
+    }
+}
+
+/// One `source-to-dest map:` section of the almanac.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryMap {
+    pub source: String,
+    pub dest: String,
+    pub rows: Vec<MapRow>,
+}
+
+impl CategoryMap {
+    pub fn name(&self) -> String {
+        format!("{}-to-{}", self.source, self.dest)
+    }
+
+    /// Reports rows that are empty, overflow, or claim source numbers an earlier row already
+    /// claims (`map_value` would silently use the earlier one).
+    pub fn issues(&self) -> Vec<AlmanacIssue> {
+        let mut issues = vec![];
+
+        for row in &self.rows {
+            if row.range_len == 0 {
+                issues.push(AlmanacIssue::ZeroLength { section: self.name(), line: row.line });
+            } else if row.overflows() {
+                issues.push(AlmanacIssue::Overflow { section: self.name(), line: row.line });
+            }
+        }
+
+        let usable_rows = self.rows
+            .iter()
+            .filter(|row| row.range_len > 0 && !row.overflows());
+        for (earlier, later) in usable_rows.tuple_combinations() {
+            let earlier_range = earlier.source_range();
+            let later_range = later.source_range();
+            if earlier_range.start < later_range.end && later_range.start < earlier_range.end {
+                issues.push(AlmanacIssue::Overlap {
+                    section: self.name(),
+                    line: later.line,
+                    other_line: earlier.line,
+                });
+            }
+        }
+
+        issues
+    }
+
+    /// Maps a single source number, passing it through unchanged when no row covers it.
+    pub fn map_value(&self, source_num: u64) -> u64 {
+        self.rows
+            .iter()
+            .find(|row| row.source_range().contains(&source_num))
+            .map(|row| row.dest_of(source_num))
+            .unwrap_or(source_num)
+    }
+
+    /// Splits `range` wherever it crosses a row boundary, recording which row (if any)
+    /// moved each piece.
+    pub fn split_range(&self, range: Range<u64>) -> Vec<Segment<'_>> {
+        let mut segments = vec![];
+
+        let mut worklist = VecDeque::from([range]);
+        while let Some(item_range) = worklist.pop_front() {
+            if item_range.is_empty() {
+                continue;
+            }
+
+            let overlapping_row = self.rows
+                .iter()
+                .find(|row| row.source_start < item_range.end && item_range.start < row.source_range().end);
+
+            match overlapping_row {
+                Some(row) => {
+                    let start = max(item_range.start, row.source_start);
+                    let end = min(item_range.end, row.source_range().end);
+                    segments.push(Segment {
+                        source: start..end,
+                        dest: row.dest_of(start)..row.dest_of(start) + (end - start),
+                        row: Some(row),
+                    });
+
+                    worklist.push_back(item_range.start..start);
+                    worklist.push_back(end..item_range.end);
+                },
+                None => {
+                    segments.push(Segment {
+                        source: item_range.clone(),
+                        dest: item_range,
+                        row: None,
+                    });
+                }
+            }
+        }
+
+        segments.sort_by_key(|segment| segment.source.start);
+        segments
+    }
+
+    /// Maps every number in `ranges`, splitting them wherever they cross a row boundary.
+    pub fn map_ranges<T>(&self, ranges: T) -> Vec<Range<u64>>
+    where
+        T: IntoIterator<Item = Range<u64>>
+    {
+        let results_set: RangeSet<u64> = ranges
+            .into_iter()
+            .flat_map(|range| self.split_range(range))
+            .map(|segment| segment.dest)
+            .collect();
+
+        results_set.iter().cloned().collect()
+    }
+
+    /// Finds every source number that maps into one of the `dest_ranges`. A row only claims the
+    /// source numbers that no earlier row covers, matching the first-match rule of `map_value`.
+    pub fn unmap_ranges<T>(&self, dest_ranges: T) -> Vec<Range<u64>>
+    where
+        T: IntoIterator<Item = Range<u64>>
+    {
+        let dest_ranges: Vec<Range<u64>> = dest_ranges
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .collect();
+
+        let mut results_set = RangeSet::new();
+        let mut covered = RangeSet::new();
+
+        for row in self.rows.iter().filter(|row| row.range_len > 0) {
+            let row_dest = row.dest_range();
+            for dest_range in &dest_ranges {
+                let start = max(dest_range.start, row_dest.start);
+                let end = min(dest_range.end, row_dest.end);
+                if start >= end {
+                    continue;
+                }
+
+                let source_start = row.source_start + (start - row.dest_start);
+                let source_range = source_start..source_start + (end - start);
+                for gap in covered.gaps(&source_range) {
+                    results_set.insert(gap);
+                }
+            }
+            covered.insert(row.source_range());
+        }
+
+        for dest_range in &dest_ranges {
+            for gap in covered.gaps(dest_range) {
+                results_set.insert(gap);
+            }
+        }
+
+        results_set.iter().cloned().collect()
+    }
+}
+
+/// A stretch of numbers that crossed one map together, and the row that moved them
+/// (`None` when they passed through unchanged).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment<'a> {
+    pub source: Range<u64>,
+    pub dest: Range<u64>,
+    pub row: Option<&'a MapRow>,
+}
+
+impl fmt::Display for Segment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.source.end - self.source.start == 1 {
+            write!(f, "{} -> {}", self.source.start, self.dest.start)?;
+        } else {
+            write!(f, "{:?} -> {:?}", self.source, self.dest)?;
+        }
+
+        match self.row {
+            Some(row) => write!(
+                f,
+                " via line {} ({} {} {})",
+                row.line, row.dest_start, row.source_start, row.range_len
+            ),
+            None => write!(f, " (pass-through)"),
+        }
+    }
+}
+
+/// Every segment produced while crossing one map of a chain.
+#[derive(Debug)]
+pub struct Hop<'a> {
+    pub map: &'a CategoryMap,
+    pub segments: Vec<Segment<'a>>,
+}
+
+impl fmt::Display for Hop<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}:", self.map.name())?;
+        for segment in &self.segments {
+            writeln!(f, "  {}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the numbers that lie in both `a` and `b`.
+pub fn intersect_ranges(a: &[Range<u64>], b: &[Range<u64>]) -> Vec<Range<u64>> {
+    let b_set = RangeSet::from_iter(b.iter().filter(|range| !range.is_empty()).cloned());
+
+    let results_set: RangeSet<u64> = a
+        .iter()
+        .filter(|range| !range.is_empty())
+        .flat_map(|range| b_set
+            .overlapping(range)
+            .map(|other| max(range.start, other.start)..min(range.end, other.end)))
+        .collect();
+
+    results_set.iter().cloned().collect()
+}
+
+/// An ordered sequence of maps leading from one category to another.
+#[derive(Debug)]
+pub struct Chain<'a> {
+    pub maps: Vec<&'a CategoryMap>,
+}
+
+impl<'a> Chain<'a> {
+    pub fn map_value(&self, source_num: u64) -> u64 {
+        self.maps
+            .iter()
+            .fold(source_num, |num, map| map.map_value(num))
+    }
+
+    pub fn map_ranges<T>(&self, ranges: T) -> Vec<Range<u64>>
+    where
+        T: IntoIterator<Item = Range<u64>>
+    {
+        self.maps
+            .iter()
+            .fold(ranges.into_iter().collect(), |ranges, map| map.map_ranges(ranges))
+    }
+
+    /// Records how `ranges` are split and moved at every map of the chain. Segments are kept
+    /// apart rather than merged, so each one can be followed back to where it came from.
+    pub fn trace<T>(&self, ranges: T) -> Vec<Hop<'a>>
+    where
+        T: IntoIterator<Item = Range<u64>>
+    {
+        let mut hops = Vec::with_capacity(self.maps.len());
+        let mut current: Vec<Range<u64>> = ranges.into_iter().collect();
+
+        for map in &self.maps {
+            let mut segments: Vec<Segment<'a>> = current
+                .into_iter()
+                .flat_map(|range| map.split_range(range))
+                .collect();
+            segments.sort_by_key(|segment| segment.source.start);
+
+            current = segments
+                .iter()
+                .map(|segment| segment.dest.clone())
+                .collect();
+            hops.push(Hop { map, segments });
+        }
+
+        hops
+    }
+
+    /// Folds the whole chain into a single precomputed map.
+    pub fn compose(&self) -> PiecewiseMap {
+        self.maps
+            .iter()
+            .fold(PiecewiseMap::identity(), |composed, map| {
+                composed.then(&PiecewiseMap::from_category_map(map))
+            })
+    }
+
+    /// Walks the chain backwards, returning every starting number that ends up in `ranges`.
+    pub fn unmap_ranges<T>(&self, ranges: T) -> Vec<Range<u64>>
+    where
+        T: IntoIterator<Item = Range<u64>>
+    {
+        self.maps
+            .iter()
+            .rev()
+            .fold(ranges.into_iter().collect(), |ranges, map| map.unmap_ranges(ranges))
+    }
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<CategoryMap>,
+}
+
+impl Almanac {
+    /// Parses an almanac whose map sections are identified by their `X-to-Y map:` headers,
+    /// so sections may appear in any order and in any number.
+    pub fn parse(input: &str) -> Result<Self, AlmanacError> {
+        let header_re = Regex::new(r"^(\w+)-to-(\w+) map:$")?;
+
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let seeds = lines
+            .next()
+            .and_then(|(_, line)| line.strip_prefix("seeds:"))
+            .ok_or(AlmanacError::MissingSeeds)?
+            .split_whitespace()
+            .map(|seed| seed.parse::<u64>().map_err(|_| AlmanacError::InvalidSeed(seed.to_string())))
+            .collect::<Result<Vec<u64>, AlmanacError>>()?;
+
+        let mut maps: Vec<CategoryMap> = vec![];
+        for (line_number, line) in lines {
+            if let Some(caps) = header_re.captures(line) {
+                let map = CategoryMap {
+                    source: caps[1].to_string(),
+                    dest: caps[2].to_string(),
+                    rows: vec![],
+                };
+
+                if maps.iter().any(|other| other.source == map.source && other.dest == map.dest) {
+                    return Err(AlmanacError::DuplicateMap(map.name()));
+                }
+                maps.push(map);
+                continue;
+            }
+
+            let map = maps
+                .last_mut()
+                .ok_or(AlmanacError::MissingHeader(line.to_string()))?;
+
+            let (dest_start, source_start, range_len) = line
+                .split_whitespace()
+                .map(|elem| elem.parse::<u64>().ok())
+                .collect_tuple()
+                .and_then(|(dest, source, len)| Some((dest?, source?, len?)))
+                .ok_or(AlmanacError::InvalidRow {
+                    section: map.name(),
+                    line: line_number,
+                    row: line.to_string(),
+                })?;
+
+            map.rows.push(MapRow { dest_start, source_start, range_len, line: line_number });
+        }
+
+        Ok(Self { seeds, maps })
+    }
+
+    pub fn issues(&self) -> Vec<AlmanacIssue> {
+        self.maps
+            .iter()
+            .flat_map(|map| map.issues())
+            .collect()
+    }
+
+    /// Refuses almanacs with any issues, since the range and composed lookups assume every
+    /// section is a set of disjoint, well-formed rows.
+    pub fn validate(&self) -> Result<(), AlmanacError> {
+        let issues = self.issues();
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(AlmanacError::Inconsistent(issues))
+        }
+    }
+
+    /// Interprets the seed list as `start length` pairs.
+    pub fn seed_ranges(&self) -> Result<Vec<Range<u64>>, AlmanacError> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(AlmanacError::UnpairedSeed(self.seeds[self.seeds.len() - 1]));
+        }
+
+        self.seeds
+            .iter()
+            .tuples()
+            .map(|(start, length)| {
+                let end = start
+                    .checked_add(*length)
+                    .ok_or(AlmanacError::SeedRangeOverflow { start: *start, length: *length })?;
+                Ok(*start..end)
+            })
+            .collect()
+    }
+
+    fn has_category(&self, category: &str) -> bool {
+        self.maps
+            .iter()
+            .any(|map| map.source == category || map.dest == category)
+    }
+
+    /// Finds the shortest sequence of maps that converts `from` numbers into `to` numbers.
+    pub fn chain(&self, from: &str, to: &str) -> Result<Chain<'_>, AlmanacError> {
+        for category in [from, to] {
+            if !self.has_category(category) {
+                return Err(AlmanacError::UnknownCategory(category.to_string()));
+            }
+        }
+
+        let mut outgoing: HashMap<&str, Vec<&CategoryMap>> = HashMap::new();
+        for map in &self.maps {
+            outgoing.entry(map.source.as_str()).or_default().push(map);
+        }
+
+        let mut previous: HashMap<&str, &CategoryMap> = HashMap::new();
+        let mut visited = HashSet::from([from]);
+        let mut stopped_at = from;
+
+        let mut worklist = VecDeque::from([from]);
+        while let Some(category) = worklist.pop_front() {
+            if category == to {
+                let mut maps = vec![];
+                let mut current = to;
+                while let Some(map) = previous.get(current) {
+                    maps.push(*map);
+                    current = &map.source;
+                }
+                maps.reverse();
+                return Ok(Chain { maps });
+            }
+
+            stopped_at = category;
+            for map in outgoing.get(category).into_iter().flatten() {
+                if visited.insert(&map.dest) {
+                    previous.insert(&map.dest, map);
+                    worklist.push_back(&map.dest);
+                }
+            }
+        }
+
+        Err(AlmanacError::BrokenChain {
+            from: from.to_string(),
+            to: to.to_string(),
+            stopped_at: stopped_at.to_string(),
+        })
+    }
+}
+
+/// The puzzle's example almanac, shared by the test modules.
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("seed", "location", 79, 82)]
+    #[case("seed", "location", 14, 43)]
+    #[case("seed", "soil", 79, 81)]
+    #[case("soil", "humidity", 81, 78)]
+    #[case("water", "water", 81, 81)]
+    fn test_chain_map_value(#[case] from: &str, #[case] to: &str, #[case] value: u64, #[case] expected: u64) -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        assert_eq!(almanac.chain(from, to)?.map_value(value), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reordered_sections() -> Result<()> {
+        let input = "\
+seeds: 1 2
+
+b-to-c map:
+10 0 5
+
+a-to-b map:
+0 1 1
+";
+        let almanac = Almanac::parse(input)?;
+        let chain = almanac.chain("a", "c")?;
+        assert_eq!(chain.maps.len(), 2);
+        assert_eq!(chain.map_value(1), 10);
+        assert_eq!(chain.map_value(2), 12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_broken_chain() -> Result<()> {
+        let input = "\
+seeds: 1
+
+seed-to-soil map:
+0 1 1
+
+water-to-location map:
+0 1 1";
+        let almanac = Almanac::parse(input)?;
+        let err = almanac.chain("seed", "location").expect_err("there is no soil-to-water map");
+        assert_eq!(
+            err.to_string(),
+            "no chain of maps leads from `seed` to `location`: it stops at `soil`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_row_line_number() {
+        let input = "\
+seeds: 1
+
+a-to-b map:
+0 1 1
+0 1";
+        let err = Almanac::parse(input).expect_err("the last row is missing a number");
+        assert_eq!(err.to_string(), "invalid row on line 5 in the `a-to-b` map: `0 1`");
+    }
+
+    #[test]
+    fn test_issues() -> Result<()> {
+        let input = "\
+seeds: 1
+
+a-to-b map:
+100 0 10
+200 5 10
+300 50 0
+0 18446744073709551610 10
+
+b-to-c map:
+0 0 5";
+        let almanac = Almanac::parse(input)?;
+        assert_eq!(almanac.issues(), vec![
+            AlmanacIssue::ZeroLength { section: "a-to-b".to_string(), line: 6 },
+            AlmanacIssue::Overflow { section: "a-to-b".to_string(), line: 7 },
+            AlmanacIssue::Overlap { section: "a-to-b".to_string(), line: 5, other_line: 4 },
+        ]);
+        assert!(matches!(almanac.validate(), Err(AlmanacError::Inconsistent(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_example_is_consistent() -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        assert!(almanac.issues().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_ranges() -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        let chain = almanac.chain("seed", "soil")?;
+        assert_eq!(chain.map_ranges(std::iter::once(95..102)), vec![50..52, 97..102]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_seed() -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        let chain = almanac.chain("seed", "water")?;
+        let hops = chain.trace(std::iter::once(79..80));
+
+        let lines: Vec<String> = hops
+            .iter()
+            .map(|hop| format!("{}: {}", hop.map.name(), hop.segments[0]))
+            .collect();
+        assert_eq!(lines, vec![
+            "seed-to-soil: 79 -> 81 via line 5 (52 50 48)",
+            "soil-to-fertilizer: 81 -> 81 (pass-through)",
+            "fertilizer-to-water: 81 -> 81 (pass-through)",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_range_split() -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        let chain = almanac.chain("seed", "soil")?;
+        let hops = chain.trace(std::iter::once(95..102));
+
+        let segments: Vec<String> = hops[0].segments
+            .iter()
+            .map(|segment| segment.to_string())
+            .collect();
+        assert_eq!(segments, vec![
+            "95..98 -> 97..100 via line 5 (52 50 48)",
+            "98..100 -> 50..52 via line 4 (50 98 2)",
+            "100..102 -> 100..102 (pass-through)",
+        ]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(50..52, vec![98..100])]
+    #[case(97..102, vec![95..98, 100..102])]
+    #[case(0..50, vec![0..50])]
+    fn test_unmap_ranges(#[case] dest_range: Range<u64>, #[case] expected: Vec<Range<u64>>) -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        let chain = almanac.chain("seed", "soil")?;
+        assert_eq!(chain.unmap_ranges(std::iter::once(dest_range)), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unmap_ranges_first_row_wins() -> Result<()> {
+        let input = "\
+seeds: 1
+
+a-to-b map:
+100 0 10
+200 5 10";
+        let almanac = Almanac::parse(input)?;
+        let chain = almanac.chain("a", "b")?;
+        assert_eq!(chain.unmap_ranges(std::iter::once(200..215)), vec![10..15, 200..215]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unmap_min_location() -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        let chain = almanac.chain("seed", "location")?;
+
+        let seeds_below = |location: u64| intersect_ranges(
+            &chain.unmap_ranges(std::iter::once(0..location)),
+            &almanac.seed_ranges().expect("the example seeds come in pairs"),
+        );
+
+        assert!(seeds_below(46).is_empty());
+        assert_eq!(seeds_below(47), vec![82..83]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("seeds: 79 14 55", "seed ranges come in `start length` pairs, but `55` has no length")]
+    #[case(
+        "seeds: 18446744073709551610 10",
+        "the seed range starting at 18446744073709551610 with length 10 runs past the largest u64",
+    )]
+    fn test_seed_ranges_errors(#[case] input: &str, #[case] message: &str) -> Result<()> {
+        let almanac = Almanac::parse(input)?;
+        assert_eq!(almanac.seed_ranges().unwrap_err().to_string(), message);
+        Ok(())
+    }
+}
\ No newline at end of file
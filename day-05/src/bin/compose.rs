@@ -0,0 +1,34 @@
+use std::fs;
+use anyhow::{bail, Result};
+use day_05::almanac::Almanac;
+use day_05::piecewise::PiecewiseMap;
+
+fn seed_to_location(input: &str) -> Result<PiecewiseMap> {
+    let almanac = Almanac::parse(input)?;
+    almanac.validate()?;
+    Ok(almanac.chain("seed", "location")?.compose())
+}
+
+/// Prints the composed seed-to-location table of the puzzle input, or with two almanac paths,
+/// the ranges where their composed tables disagree.
+fn main() -> Result<()> {
+    let paths: Vec<String> = std::env::args().skip(1).collect();
+
+    match &paths[..] {
+        [] => {
+            let file = include_str!("../../input2.txt");
+            print!("{}", seed_to_location(file)?);
+        },
+        [path_a, path_b] => {
+            let map_a = seed_to_location(&fs::read_to_string(path_a)?)?;
+            let map_b = seed_to_location(&fs::read_to_string(path_b)?)?;
+
+            for (range, offset_a, offset_b) in map_a.diff(&map_b) {
+                println!("{:?}: {:+} vs {:+}", range, offset_a, offset_b);
+            }
+        },
+        _ => bail!("usage: compose [almanac_a almanac_b]"),
+    }
+
+    Ok(())
+}
\ No newline at end of file
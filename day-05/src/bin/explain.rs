@@ -0,0 +1,31 @@
+use anyhow::{bail, Result};
+use day_05::almanac::Almanac;
+
+/// Prints every seed-to-location hop for `seed`, or for the range `start length` when given
+/// two numbers, showing which map row moved each piece.
+fn main() -> Result<()> {
+    let args = std::env::args()
+        .skip(1)
+        .map(|arg| arg.parse::<u64>())
+        .collect::<Result<Vec<u64>, _>>()?;
+
+    let (start, length) = match args[..] {
+        [seed] => (seed, 1),
+        [start, length] => (start, length),
+        _ => bail!("usage: explain seed [length]"),
+    };
+    let Some(end) = start.checked_add(length) else {
+        bail!("usage: explain seed [length], where seed + length fits in a u64");
+    };
+    let seeds = start..end;
+
+    let file = include_str!("../../input2.txt");
+    let almanac = Almanac::parse(file)?;
+    almanac.validate()?;
+
+    for hop in almanac.chain("seed", "location")?.trace(std::iter::once(seeds)) {
+        print!("{}", hop);
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use std::iter::once;
+use anyhow::{bail, Result};
+use day_05::almanac::{intersect_ranges, Almanac};
+
+/// Lists the seed ranges that end up at a location in `[start] end`, e.g. `inverse 46`
+/// shows every seed whose location is below 46.
+fn main() -> Result<()> {
+    let args = std::env::args()
+        .skip(1)
+        .map(|arg| arg.parse::<u64>())
+        .collect::<Result<Vec<u64>, _>>()?;
+
+    let locations = match args[..] {
+        [end] => 0..end,
+        [start, end] => start..end,
+        _ => bail!("usage: inverse [start] end"),
+    };
+
+    let file = include_str!("../../input2.txt");
+    let almanac = Almanac::parse(file)?;
+    almanac.validate()?;
+    let seed_to_location = almanac.chain("seed", "location")?;
+
+    let all_seeds = seed_to_location.unmap_ranges(once(locations.clone()));
+    println!("seeds mapping to locations {:?}:", locations);
+    for range in &all_seeds {
+        println!("  {:?}", range);
+    }
+
+    println!("of which are listed in the seed ranges:");
+    for range in intersect_ranges(&all_seeds, &almanac.seed_ranges()?) {
+        println!("  {:?}", range);
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,20 @@
+use std::fs;
+use anyhow::Result;
+use day_05::almanac::Almanac;
+
+/// Lists every issue in the almanac at the given path, or in the puzzle input by default.
+fn main() -> Result<()> {
+    let input = match std::env::args().nth(1) {
+        Some(path) => fs::read_to_string(path)?,
+        None => include_str!("../../input2.txt").to_string(),
+    };
+
+    let almanac = Almanac::parse(&input)?;
+    let issues = almanac.issues();
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    println!("{} issue(s) found", issues.len());
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,2 +1,4 @@
+pub mod almanac;
 pub mod part1;
-pub mod part2;
\ No newline at end of file
+pub mod part2;
+pub mod piecewise;
\ No newline at end of file
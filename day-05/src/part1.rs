@@ -1,60 +1,16 @@
-use std::cmp::min;
-use anyhow::Result;
-use itertools::Itertools;
-
-fn source_to_dest(source_num: u64, ranges: &Vec<(u64, u64, u64)>) -> u64 {
-    for (dest_start, source_start, range_len) in ranges {
-        if source_num >= *source_start && source_num < *source_start + *range_len {
-            let difference = source_num - *source_start;
-            return *dest_start + difference;
-        }
-    }
-
-    return source_num;
-}
-
-fn parse_section_ranges(section: &str) -> Vec<(u64, u64, u64)> {
-    section
-        .split('\n')
-        .skip(1)
-        .map(|row| {
-            row.split(' ')
-                .map(|elem| elem.parse::<u64>().expect("range elem is an integer"))
-                .collect_tuple().expect("rows should have exactly three integers")
-        })
-        .collect()
-}
+use anyhow::{anyhow, Result};
+use crate::almanac::Almanac;
 
 pub fn process(input: &str) -> Result<String> {
-    let sections: Vec<&str> = input.split("\n\n").collect();
-    assert_eq!(sections.len(), 8);
-
-    let starting_seeds = sections[0]
-        .strip_prefix("seeds: ")
-        .expect("first line lists the seeds")
-        .split(' ')
-        .map(|seed| seed.parse::<u64>().expect("seed is numeric"));
-
-    let seed_to_soil_map = parse_section_ranges(sections[1]);
-    let soil_to_fertilizer_map = parse_section_ranges(sections[2]);
-    let fertilizer_to_water_map = parse_section_ranges(sections[3]);
-    let water_to_light_map = parse_section_ranges(sections[4]);
-    let light_to_temperature_map = parse_section_ranges(sections[5]);
-    let temperature_to_humidity_map = parse_section_ranges(sections[6]);
-    let humidity_to_location_map = parse_section_ranges(sections[7]);
+    let almanac = Almanac::parse(input)?;
+    almanac.validate()?;
+    let seed_to_location = almanac.chain("seed", "location")?.compose();
 
-    let locations = starting_seeds
-        .map(move |seed| {
-            let soil = source_to_dest(seed, &seed_to_soil_map);
-            let fertilizer = source_to_dest(soil, &soil_to_fertilizer_map);
-            let water = source_to_dest(fertilizer, &fertilizer_to_water_map);
-            let light = source_to_dest(water, &water_to_light_map);
-            let temperature = source_to_dest(light, &light_to_temperature_map);
-            let humidity = source_to_dest(temperature, &temperature_to_humidity_map);
-            source_to_dest(humidity, &humidity_to_location_map)
-        });
-
-    let min_location = locations.reduce(min).expect("there is at least one location");
+    let min_location = almanac.seeds
+        .iter()
+        .map(|seed| seed_to_location.apply(*seed))
+        .min()
+        .ok_or(anyhow!("there is at least one seed"))?;
 
     Ok(min_location.to_string())
 }
@@ -102,4 +58,17 @@ humidity-to-location map:
         assert_eq!("35", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_process_max_seed() -> Result<()> {
+        let input = "\
+seeds: 18446744073709551615 5
+
+seed-to-location map:
+0 5 1
+7 18446744073709551614 1";
+        assert_eq!("0", process(input)?);
+        assert_eq!("18446744073709551615", process(&input.replace(" 5\n", "\n"))?);
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -1,148 +1,18 @@
-use std::cmp::{min, Ordering};
-use std::collections::LinkedList;
-use std::ops::Range;
-use anyhow::Result;
-use itertools::Itertools;
-use rangemap::{RangeMap, RangeSet};
-
-#[derive(Ord)]
-struct Bound(u64, u64);
-
-impl Eq for Bound {}
-
-impl PartialEq<Self> for Bound {
-    fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
-    }
-}
-
-impl PartialOrd<Self> for Bound {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self.0 < other.0 {
-            Some(Ordering::Less)
-        } else if self.0 > other.0 {
-            Some(Ordering::Greater)
-        } else {
-            Some(Ordering::Equal)
-        }
-    }
-}
-
-fn parse_section_ranges(section: &str) -> Vec<(u64, u64, u64)> {
-    section
-        .split('\n')
-        .skip(1)
-        .map(|row| {
-            row.split(' ')
-                .map(|elem| elem.parse::<u64>().expect("range elem is an integer"))
-                .collect_tuple::<(u64, u64, u64)>().expect("rows should have exactly three integers")
-        })
-        .collect()
-}
-
-fn process_item_ranges<I, T>(item_ranges: I, dest_mappings: T) -> Vec<Bound>
-where
-I: IntoIterator<Item = Bound>,
-T: IntoIterator<Item = (Bound, u64)>
-{
-    let mut dest_mappings_iter = dest_mappings.into_iter().peekable();
-    let mut results = vec![];
-
-    let mut worklist = LinkedList::from_iter(item_ranges);
-    while let Some(item_bound) = worklist.pop_front() {
-        let dest_bound = dest_mappings_iter.next();
-
-        match dest_mappings_iter.peek() {
-            Some(dest_mapping) => {
-            },
-            None => {
-
-            }
-        }
-    }
-
-    results.sort();
-    results
-}
-
-fn process_ranges<T>(ranges: T, source_to_dest_map: &RangeMap<u64, u64>) -> Vec<Range<u64>>
-where
-T: IntoIterator<Item = Range<u64>>
-{
-    let mut results_set = RangeSet::new();
-
-    let mut worklist = LinkedList::from_iter(ranges);
-
-    while let Some(item_range) = worklist.pop_front() {
-        let first_item = item_range.start;
-        let last_item = item_range.end;
-        match source_to_dest_map.get_key_value(&first_item) {
-            Some((source_range, dest_start)) => {
-                let num_elements = min(
-                    last_item - first_item,
-                    last_item - source_range.start,
-                );
-                let num_remaining_elements = last_item - (first_item + num_elements);
-
-                let dest_range = *dest_start..(*dest_start + num_elements);
-
-                results_set.insert(dest_range);
-
-                if num_remaining_elements > 0 {
-                    worklist.push_back(first_item+num_elements..last_item);
-                }
-            },
-            None => {
-                results_set.insert(first_item..first_item+1);
-
-                if first_item + 1 < last_item {
-                    worklist.push_back(first_item+1..last_item);
-                }
-            }
-        };
-    }
-
-    results_set
-        .iter()
-        .map(|range| range.clone())
-        .collect()
-}
+use anyhow::{anyhow, Result};
+use crate::almanac::Almanac;
 
 pub fn process(input: &str) -> Result<String> {
-    let sections: Vec<&str> = input.split("\n\n").collect();
-    assert_eq!(sections.len(), 8);
-
-    let starting_seed_ranges: Vec<Range<u64>> = sections[0]
-        .strip_prefix("seeds: ")
-        .expect("first line lists the seeds")
-        .split(' ')
-        .map(|seed| seed.parse::<u64>().expect("seed part is numeric"))
-        .tuples::<(u64, u64)>()
-        .map(|(start, length)| start..start+length)
-        .collect();
-
-
-    let seed_to_soil_map = parse_section_ranges(sections[1]);
-    let soil_to_fertilizer_map = parse_section_ranges(sections[2]);
-    let fertilizer_to_water_map = parse_section_ranges(sections[3]);
-    let water_to_light_map = parse_section_ranges(sections[4]);
-    let light_to_temperature_map = parse_section_ranges(sections[5]);
-    let temperature_to_humidity_map = parse_section_ranges(sections[6]);
-    let humidity_to_location_map = parse_section_ranges(sections[7]);
-
-    let soil_ranges = process_ranges(starting_seed_ranges, &seed_to_soil_map);
-    let fertilizer_ranges = process_ranges(soil_ranges, &soil_to_fertilizer_map);
-    let water_ranges = process_ranges(fertilizer_ranges, &fertilizer_to_water_map);
-    let light_ranges = process_ranges(water_ranges, &water_to_light_map);
-    let temperature_ranges = process_ranges(light_ranges, &light_to_temperature_map);
-    let humidity_ranges = process_ranges(temperature_ranges, &temperature_to_humidity_map);
-    let location_ranges = process_ranges(humidity_ranges, &humidity_to_location_map);
-
-    let min_location = location_ranges
-        .iter()
-        .map(|range| range.start)
-        .reduce(min)
-        .expect("there is at least one location");
+    let almanac = Almanac::parse(input)?;
+    almanac.validate()?;
+    let seed_to_location = almanac.chain("seed", "location")?.compose();
+
+    let min_location = almanac
+        .seed_ranges()?
+        .into_iter()
+        .filter(|seeds| !seeds.is_empty())
+        .filter_map(|seeds| seed_to_location.min_over(seeds))
+        .min()
+        .ok_or(anyhow!("there is at least one seed range"))?;
 
     Ok(min_location.to_string())
 }
@@ -190,4 +60,15 @@ humidity-to-location map:
         assert_eq!("46", process(input)?);
         Ok(())
     }
-}
+
+    #[test]
+    fn test_process_empty_range() -> Result<()> {
+        let input = "\
+seeds: 80 0 200 5
+
+seed-to-location map:
+0 79 5";
+        assert_eq!("200", process(input)?);
+        Ok(())
+    }
+}
\ No newline at end of file
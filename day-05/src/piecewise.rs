@@ -0,0 +1,244 @@
+use std::cmp::{max, min};
+use std::fmt;
+use std::ops::Range;
+use rangemap::RangeSet;
+use crate::almanac::CategoryMap;
+
+/// A contiguous run of numbers that all move by the same `offset`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piece {
+    pub source: Range<u64>,
+    pub offset: i128,
+}
+
+impl Piece {
+    fn shift(&self, num: u64) -> u64 {
+        u64::try_from(num as i128 + self.offset).expect("mapped numbers fit in a u64")
+    }
+
+    fn image(&self) -> Range<u64> {
+        self.shift(self.source.start)..self.shift(self.source.end - 1) + 1
+    }
+}
+
+/// A function over `0..u64::MAX` stored as sorted, non-overlapping pieces that cover the
+/// whole domain, so lookups are a binary search and two maps can be composed piece by piece.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    pieces: Vec<Piece>,
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> Self {
+        Self {
+            pieces: vec![Piece { source: 0..u64::MAX, offset: 0 }],
+        }
+    }
+
+    /// Builds a map from pieces that may leave gaps, filling the gaps with the identity and
+    /// merging neighbours that share an offset.
+    fn from_sparse(mut pieces: Vec<Piece>) -> Self {
+        pieces.sort_by_key(|piece| piece.source.start);
+
+        let mut filled: Vec<Piece> = Vec::with_capacity(pieces.len() * 2 + 1);
+        let mut next_start = 0;
+        for piece in pieces {
+            if next_start < piece.source.start {
+                filled.push(Piece { source: next_start..piece.source.start, offset: 0 });
+            }
+            next_start = piece.source.end;
+            filled.push(piece);
+        }
+        if next_start < u64::MAX {
+            filled.push(Piece { source: next_start..u64::MAX, offset: 0 });
+        }
+
+        let mut merged: Vec<Piece> = Vec::with_capacity(filled.len());
+        for piece in filled {
+            match merged.last_mut() {
+                Some(last) if last.offset == piece.offset && last.source.end == piece.source.start => {
+                    last.source.end = piece.source.end;
+                },
+                _ => merged.push(piece),
+            }
+        }
+
+        Self { pieces: merged }
+    }
+
+    pub fn from_category_map(map: &CategoryMap) -> Self {
+        let mut covered = RangeSet::new();
+        let mut pieces = vec![];
+
+        for row in map.rows.iter().filter(|row| row.range_len > 0) {
+            let offset = row.dest_start as i128 - row.source_start as i128;
+            for source in covered.gaps(&row.source_range()) {
+                pieces.push(Piece { source, offset });
+            }
+            covered.insert(row.source_range());
+        }
+
+        Self::from_sparse(pieces)
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    /// Index of the first piece whose source ends after `num`.
+    fn piece_index(&self, num: u64) -> usize {
+        self.pieces.partition_point(|piece| piece.source.end <= num)
+    }
+
+    pub fn apply(&self, num: u64) -> u64 {
+        // The pieces stop just short of u64::MAX. No row's range can include it either, so
+        // every map, and therefore every composition, leaves it alone.
+        if num == u64::MAX {
+            return num;
+        }
+        self.pieces[self.piece_index(num)].shift(num)
+    }
+
+    /// The smallest value the map produces over `range`, or `None` if the range is empty.
+    /// Every piece is increasing, so only the first number of each overlapping piece needs to
+    /// be checked.
+    pub fn min_over(&self, range: Range<u64>) -> Option<u64> {
+        if range.is_empty() {
+            return None;
+        }
+
+        self.pieces[self.piece_index(range.start)..]
+            .iter()
+            .take_while(|piece| piece.source.start < range.end)
+            .map(|piece| piece.shift(max(piece.source.start, range.start)))
+            .min()
+    }
+
+    /// Returns the map that applies `self` first and then `next`.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut pieces = vec![];
+
+        for piece in &self.pieces {
+            let image = piece.image();
+            for next_piece in next.pieces[next.piece_index(image.start)..]
+                .iter()
+                .take_while(|next_piece| next_piece.source.start < image.end)
+            {
+                let start = max(image.start, next_piece.source.start);
+                let end = min(image.end, next_piece.source.end);
+                let source_start = (start as i128 - piece.offset) as u64;
+
+                pieces.push(Piece {
+                    source: source_start..source_start + (end - start),
+                    offset: piece.offset + next_piece.offset,
+                });
+            }
+        }
+
+        Self::from_sparse(pieces)
+    }
+
+    /// Lists the ranges where the two maps disagree, with the offset each one applies there.
+    pub fn diff(&self, other: &PiecewiseMap) -> Vec<(Range<u64>, i128, i128)> {
+        let mut differences: Vec<(Range<u64>, i128, i128)> = vec![];
+
+        let (mut i, mut j) = (0, 0);
+        while i < self.pieces.len() && j < other.pieces.len() {
+            let (ours, theirs) = (&self.pieces[i], &other.pieces[j]);
+            let start = max(ours.source.start, theirs.source.start);
+            let end = min(ours.source.end, theirs.source.end);
+
+            if ours.offset != theirs.offset {
+                match differences.last_mut() {
+                    Some((range, a, b)) if range.end == start && *a == ours.offset && *b == theirs.offset => {
+                        range.end = end;
+                    },
+                    _ => differences.push((start..end, ours.offset, theirs.offset)),
+                }
+            }
+
+            if ours.source.end == end {
+                i += 1;
+            }
+            if theirs.source.end == end {
+                j += 1;
+            }
+        }
+
+        differences
+    }
+}
+
+impl fmt::Display for PiecewiseMap {
+    /// Writes one `source_start source_end offset` row per piece.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:>20} {:>20} {:>12}", "source_start", "source_end", "offset")?;
+        for piece in &self.pieces {
+            writeln!(f, "{:>20} {:>20} {:>+12}", piece.source.start, piece.source.end, piece.offset)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::almanac::{Almanac, EXAMPLE};
+    use anyhow::Result;
+    use rstest::rstest;
+
+    #[test]
+    fn test_from_category_map() -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        let map = PiecewiseMap::from_category_map(&almanac.maps[0]);
+        assert_eq!(map.pieces(), &[
+            Piece { source: 0..50, offset: 0 },
+            Piece { source: 50..98, offset: 2 },
+            Piece { source: 98..100, offset: -48 },
+            Piece { source: 100..u64::MAX, offset: 0 },
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_composed_matches_chain() -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        let chain = almanac.chain("seed", "location")?;
+        let composed = chain.compose();
+
+        for seed in (0..200).chain([u64::MAX - 1, u64::MAX]) {
+            assert_eq!(composed.apply(seed), chain.map_value(seed), "seed {}", seed);
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(79..93, 46)]
+    #[case(55..68, 56)]
+    #[case(82..83, 46)]
+    fn test_min_over(#[case] seeds: Range<u64>, #[case] expected: u64) -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        let composed = almanac.chain("seed", "location")?.compose();
+        assert_eq!(composed.min_over(seeds), Some(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_over_empty() -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        let composed = almanac.chain("seed", "location")?.compose();
+        assert_eq!(composed.min_over(82..82), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff() -> Result<()> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        let seed_to_soil = PiecewiseMap::from_category_map(&almanac.maps[0]);
+        let identity = PiecewiseMap::identity();
+
+        assert!(seed_to_soil.diff(&seed_to_soil).is_empty());
+        assert_eq!(seed_to_soil.diff(&identity), vec![(50..98, 2, 0), (98..100, -48, 0)]);
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use anyhow::Result;
+use day_06::race::{kern, parse_races};
+
+/// Prints the report for every race on the sheet, then for the kerned race.
+fn main() -> Result<()> {
+    let file = include_str!("../../input1.txt");
+    let races = parse_races(file)?;
+
+    for race in &races {
+        println!("{}", race.report());
+    }
+    println!("kerned: {}", kern(&races)?.report());
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,263 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use anyhow::{anyhow, bail, Result};
+
+/// Integer square root (the largest `x` with `x * x <= n`) via Newton's method, so the
+/// result stays exact far beyond the 53 bits an `f64` could represent.
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    let bits = 128 - n.leading_zeros();
+    let mut x = 1u128 << bits.div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Finds the charge times `h` in `0..=time_limit` for which `h * (time_limit - h)` beats
+/// `record_distance`, or `None` if no charge time does.
+///
+/// The winning times are the integers strictly between the roots of
+/// `h^2 - time_limit * h + record_distance = 0`, so we estimate the lower root with an
+/// integer square root of the discriminant and then nudge it onto the first winning time.
+/// The upper bound follows by symmetry around `time_limit / 2`.
+fn winning_hold_times(time_limit: u64, record_distance: u64) -> Option<RangeInclusive<u64>> {
+    let time_limit = time_limit as u128;
+    let record_distance = record_distance as u128;
+    let wins = |charge_up_time: u128| charge_up_time * (time_limit - charge_up_time) > record_distance;
+
+    let discriminant = (time_limit * time_limit).checked_sub(4 * record_distance)?;
+    if discriminant == 0 {
+        return None;
+    }
+
+    let mut min_charge_up_time = (time_limit - isqrt(discriminant)) / 2;
+    while min_charge_up_time <= time_limit / 2 && !wins(min_charge_up_time) {
+        min_charge_up_time += 1;
+    }
+    if min_charge_up_time > time_limit / 2 {
+        return None;
+    }
+    while min_charge_up_time > 0 && wins(min_charge_up_time - 1) {
+        min_charge_up_time -= 1;
+    }
+
+    let max_charge_up_time = time_limit - min_charge_up_time;
+    Some(min_charge_up_time as u64..=max_charge_up_time as u64)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time_limit: u64,
+    pub record_distance: u64,
+}
+
+impl Race {
+    /// Holding the button past the time limit leaves no time to move, so it goes nowhere.
+    pub fn distance(&self, charge_up_time: u64) -> u128 {
+        let speed = charge_up_time as u128; // just making this distinction clear
+        speed * self.time_limit.saturating_sub(charge_up_time) as u128
+    }
+
+    pub fn report(&self) -> RaceReport {
+        let optimal_hold_time = self.time_limit / 2;
+        let best_distance = self.distance(optimal_hold_time);
+
+        RaceReport {
+            race: *self,
+            winning_hold_times: winning_hold_times(self.time_limit, self.record_distance),
+            optimal_hold_time,
+            winning_margin: best_distance.saturating_sub(self.record_distance as u128),
+        }
+    }
+}
+
+/// Everything worth knowing about how to win a race.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceReport {
+    pub race: Race,
+    pub winning_hold_times: Option<RangeInclusive<u64>>,
+    /// Holding for half the race goes furthest (`time_limit / 2` and its ceiling tie).
+    pub optimal_hold_time: u64,
+    /// How far the optimal hold beats the record by, or 0 if it doesn't.
+    pub winning_margin: u128,
+}
+
+impl RaceReport {
+    pub fn ways_to_win(&self) -> u64 {
+        self.winning_hold_times
+            .as_ref()
+            .map_or(0, |hold_times| hold_times.end() - hold_times.start() + 1)
+    }
+}
+
+impl fmt::Display for RaceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "time {}, record {}: ", self.race.time_limit, self.race.record_distance)?;
+        match &self.winning_hold_times {
+            Some(hold_times) => write!(
+                f,
+                "win by holding {}..={} ({} ways), best hold {} beats the record by {}",
+                hold_times.start(), hold_times.end(), self.ways_to_win(),
+                self.optimal_hold_time, self.winning_margin
+            ),
+            None => write!(f, "cannot be won (best hold {})", self.optimal_hold_time),
+        }
+    }
+}
+
+fn parse_numbers(input: &str, label: &str) -> Result<Vec<u64>> {
+    let line = input
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(label))
+        .ok_or(anyhow!("the input has no `{}` line", label))?;
+
+    line.split_whitespace()
+        .map(|number| number.parse::<u64>().map_err(|_| anyhow!("invalid number in `{}` line: `{}`", label, number)))
+        .collect()
+}
+
+/// Reads the races from the `Time:` and `Distance:` lines, pairing the numbers column by column.
+pub fn parse_races(input: &str) -> Result<Vec<Race>> {
+    let times = parse_numbers(input, "Time:")?;
+    let record_distances = parse_numbers(input, "Distance:")?;
+
+    if times.len() != record_distances.len() {
+        bail!("there are {} times but {} record distances", times.len(), record_distances.len());
+    }
+
+    Ok(times
+        .into_iter()
+        .zip(record_distances)
+        .map(|(time_limit, record_distance)| Race { time_limit, record_distance })
+        .collect())
+}
+
+fn kern_numbers(numbers: impl Iterator<Item = u64>) -> Result<u64> {
+    let digits: String = numbers.map(|number| number.to_string()).collect();
+    digits
+        .parse()
+        .map_err(|_| anyhow!("kerned number `{}` does not fit in a u64", digits))
+}
+
+/// Fixes the bad kerning on the sheet by joining the digits of every race into one race.
+pub fn kern(races: &[Race]) -> Result<Race> {
+    if races.is_empty() {
+        bail!("there are no races to kern");
+    }
+
+    Ok(Race {
+        time_limit: kern_numbers(races.iter().map(|race| race.time_limit))?,
+        record_distance: kern_numbers(races.iter().map(|race| race.record_distance))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(2, 10)]
+    #[case(7, 0)]
+    #[case(8, 0)]
+    #[case(u64::MAX, 0)]
+    fn test_distance(#[case] charge_up_time: u64, #[case] expected: u128) {
+        let race = Race { time_limit: 7, record_distance: 9 };
+        assert_eq!(race.distance(charge_up_time), expected);
+    }
+
+    fn brute_force_hold_times(race: &Race) -> Vec<u64> {
+        (0..=race.time_limit)
+            .filter(|charge_up_time| race.distance(*charge_up_time) > race.record_distance as u128)
+            .collect()
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(15)]
+    #[case(16)]
+    #[case(17)]
+    #[case(u64::MAX as u128)]
+    #[case(u128::MAX)]
+    fn test_isqrt(#[case] n: u128) {
+        let root = isqrt(n);
+        assert!(root * root <= n);
+        assert!((root + 1).checked_mul(root + 1).is_none_or(|square| square > n));
+    }
+
+    #[rstest]
+    #[case(7, 9, 4)]
+    #[case(15, 40, 8)]
+    #[case(30, 200, 9)]
+    #[case(71530, 940200, 71503)]
+    #[case(3, 2, 0)]
+    #[case(4, 4, 0)]
+    #[case(0, 0, 0)]
+    #[case(u64::MAX, 0, u64::MAX - 1)]
+    fn test_ways_to_win(#[case] time_limit: u64, #[case] previous_record: u64, #[case] expected: u64) {
+        let race = Race { time_limit, record_distance: previous_record };
+        assert_eq!(race.report().ways_to_win(), expected);
+    }
+
+    #[test]
+    fn test_report() {
+        let report = Race { time_limit: 7, record_distance: 9 }.report();
+        assert_eq!(report.winning_hold_times, Some(2..=5));
+        assert_eq!(report.optimal_hold_time, 3);
+        assert_eq!(report.winning_margin, 3);
+        assert_eq!(
+            report.to_string(),
+            "time 7, record 9: win by holding 2..=5 (4 ways), best hold 3 beats the record by 3"
+        );
+    }
+
+    #[test]
+    fn test_parse_and_kern() -> Result<()> {
+        let races = parse_races("Time:      7  15   30\nDistance:  9  40  200\n")?;
+        assert_eq!(races, vec![
+            Race { time_limit: 7, record_distance: 9 },
+            Race { time_limit: 15, record_distance: 40 },
+            Race { time_limit: 30, record_distance: 200 },
+        ]);
+        assert_eq!(kern(&races)?, Race { time_limit: 71530, record_distance: 940200 });
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Time: 7 15\nDistance: 9")]
+    #[case("Time: 7\n")]
+    #[case("Time: 7\nDistance: x")]
+    fn test_parse_races_invalid(#[case] input: &str) {
+        assert!(parse_races(input).is_err());
+    }
+
+    #[test]
+    fn test_matches_brute_force_on_small_races() {
+        // every record from zero to just past the best possible distance
+        let races = (0..80u64).flat_map(|time_limit| {
+            (0..=time_limit * time_limit / 4 + 2).map(move |record_distance| (time_limit, record_distance))
+        });
+
+        for (time_limit, record_distance) in races {
+            let race = Race { time_limit, record_distance };
+            let hold_times = brute_force_hold_times(&race);
+            let report = race.report();
+
+            assert_eq!(report.ways_to_win(), hold_times.len() as u64, "{:?}", race);
+            assert_eq!(
+                report.winning_hold_times,
+                hold_times.first().zip(hold_times.last()).map(|(min, max)| *min..=*max),
+                "{:?}", race
+            );
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::time::Instant;
+use anyhow::{ensure, Result};
+use day_07::camel::{self, Rules};
+use day_07::compact::{self, generate_hands};
+
+/// Times sorting `Hand`s against sorting compact integer keys on generated hands.
+/// Run as `cargo run --release --bin bench [num_hands]`.
+fn main() -> Result<()> {
+    let num_hands = match std::env::args().nth(1) {
+        Some(num_hands) => num_hands.parse()?,
+        None => 1_000_000,
+    };
+
+    for (name, rules) in [("standard", Rules::standard()), ("jokers", Rules::jokers())] {
+        let input = generate_hands(num_hands, 2023, &rules);
+
+        let start = Instant::now();
+        let hand_total = camel::process(&input, &rules)?;
+        let hand_time = start.elapsed();
+
+        let start = Instant::now();
+        let key_total = compact::total_winnings(&input, &rules)?;
+        let key_time = start.elapsed();
+
+        ensure!(hand_total == key_total, "{} rules: totals differ ({} vs {})", name, hand_total, key_total);
+        println!("{} rules, {} hands: Hand sort {:?}, compact keys {:?}", name, num_hands, hand_time, key_time);
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+use std::fs;
+use anyhow::{bail, Result};
+use day_07::camel::{explain, Rules};
+
+/// Lists every hand with its type, what its jokers became, and its share of the winnings.
+/// Run as `explain <1|2> [path]`; part 2 plays with jokers.
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let (rules, default_input) = match args.first().map(|part| part.as_str()) {
+        Some("1") => (Rules::standard(), include_str!("../../input1.txt")),
+        Some("2") => (Rules::jokers(), include_str!("../../input2.txt")),
+        _ => bail!("usage: explain <1|2> [path]"),
+    };
+    let input = match args.get(1) {
+        Some(path) => fs::read_to_string(path)?,
+        None => default_input.to_string(),
+    };
+
+    let explanations = explain(&input, &rules)?;
+    for explanation in &explanations {
+        println!("{}", explanation);
+    }
+
+    let total: u128 = explanations.iter().map(|explanation| explanation.winnings() as u128).sum();
+    println!("total winnings: {}", total);
+
+    Ok(())
+}
\ No newline at end of file
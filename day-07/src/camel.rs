@@ -0,0 +1,542 @@
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum HandError {
+    #[error("invalid hand line: `{0}`")]
+    InvalidLine(String),
+
+    #[error("`{0}` is not a card")]
+    UnknownCard(char),
+
+    #[error("a hand has {expected} cards, but `{cards}` has {actual}")]
+    WrongSize { cards: String, expected: usize, actual: usize },
+
+    #[error("invalid bid amount: `{0}`")]
+    InvalidBid(String),
+
+    #[error("rank `{0}` appears more than once in the card order")]
+    DuplicateRank(char),
+
+    #[error("wildcard `{0}` is not one of the ranks")]
+    UnknownWildcard(char),
+
+    #[error("the hand-type ladder is empty")]
+    EmptyLadder,
+
+    #[error("`{0}` does not fit any hand type on the ladder")]
+    Unclassified(String),
+
+    #[error("unsupported rules: {0}")]
+    UnsupportedRules(String),
+}
+
+/// A named hand type and the sizes of the groups of matching cards it needs, e.g. a full
+/// house needs a group of 3 and a group of 2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandCategory {
+    pub name: String,
+    groups: Vec<u32>,
+}
+
+impl HandCategory {
+    pub fn new(name: &str, groups: &[u32]) -> Self {
+        let mut groups = groups.to_vec();
+        groups.sort_by(|a, b| b.cmp(a));
+
+        Self { name: name.to_string(), groups }
+    }
+
+    /// Whether a hand with these group sizes (largest first) can form this category, with
+    /// wildcards making up any shortfall. Pairing the largest groups with the largest
+    /// requirements never needs more wildcards than any other pairing.
+    pub(crate) fn is_formed_by(&self, groups: &[u32], num_wildcards: u32) -> bool {
+        let shortfall: u32 = self.groups
+            .iter()
+            .enumerate()
+            .map(|(i, needed)| needed.saturating_sub(groups.get(i).copied().unwrap_or(0)))
+            .sum();
+
+        shortfall <= num_wildcards
+    }
+}
+
+/// The usual Camel Cards hand types, weakest first.
+pub fn standard_ladder() -> Vec<HandCategory> {
+    vec![
+        HandCategory::new("high card", &[]),
+        HandCategory::new("one pair", &[2]),
+        HandCategory::new("two pair", &[2, 2]),
+        HandCategory::new("three of a kind", &[3]),
+        HandCategory::new("full house", &[3, 2]),
+        HandCategory::new("four of a kind", &[4]),
+        HandCategory::new("five of a kind", &[5]),
+    ]
+}
+
+/// Which cards exist, how they rank against each other for tie-breaks, which one (if any) is
+/// a wildcard, how many cards make a hand, and the ladder of hand types they are scored by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    ranks: Vec<char>,
+    wildcard: Option<char>,
+    hand_size: usize,
+    ladder: Vec<HandCategory>,
+}
+
+impl Rules {
+    /// Builds 5-card rules on the standard ladder from the card labels ordered weakest first,
+    /// e.g. `"23456789TJQKA"`.
+    pub fn new(ranks: &str, wildcard: Option<char>) -> Result<Self, HandError> {
+        let ranks: Vec<char> = ranks.chars().collect();
+
+        for (i, label) in ranks.iter().enumerate() {
+            if ranks[..i].contains(label) {
+                return Err(HandError::DuplicateRank(*label));
+            }
+        }
+
+        if let Some(wildcard) = wildcard {
+            if !ranks.contains(&wildcard) {
+                return Err(HandError::UnknownWildcard(wildcard));
+            }
+        }
+
+        Ok(Self {
+            ranks,
+            wildcard,
+            hand_size: 5,
+            ladder: standard_ladder(),
+        })
+    }
+
+    pub fn standard() -> Self {
+        Self::new("23456789TJQKA", None).expect("standard rules are valid")
+    }
+
+    /// Jacks become jokers: wildcards that are individually the weakest card.
+    pub fn jokers() -> Self {
+        Self::new("J23456789TQKA", Some('J')).expect("joker rules are valid")
+    }
+
+    pub fn with_hand_size(mut self, hand_size: usize) -> Self {
+        self.hand_size = hand_size;
+        self
+    }
+
+    /// Replaces the hand types, which must be ordered weakest first.
+    pub fn with_ladder(mut self, ladder: Vec<HandCategory>) -> Result<Self, HandError> {
+        if ladder.is_empty() {
+            return Err(HandError::EmptyLadder);
+        }
+        self.ladder = ladder;
+        Ok(self)
+    }
+
+    pub fn ranks(&self) -> &[char] {
+        &self.ranks
+    }
+
+    pub fn wildcard(&self) -> Option<char> {
+        self.wildcard
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    pub fn ladder(&self) -> &[HandCategory] {
+        &self.ladder
+    }
+
+    pub fn card(&self, label: char) -> Result<Card, HandError> {
+        self.ranks
+            .iter()
+            .position(|rank_label| *rank_label == label)
+            .map(|rank| Card { rank: rank as u8, label })
+            .ok_or(HandError::UnknownCard(label))
+    }
+
+    /// Groups the non-wildcard cards as `(size, rank)`, largest (then highest) first, along
+    /// with the number of wildcards.
+    fn groups(&self, cards: &[Card]) -> (Vec<(u32, u8)>, u32) {
+        let mut counts = vec![0; self.ranks.len()];
+        let mut num_wildcards = 0;
+        for card in cards {
+            if Some(card.label) == self.wildcard {
+                num_wildcards += 1;
+            } else {
+                counts[card.rank as usize] += 1;
+            }
+        }
+
+        let mut groups: Vec<(u32, u8)> = counts
+            .into_iter()
+            .enumerate()
+            .filter(|(_, count)| *count > 0)
+            .map(|(rank, count)| (count, rank as u8))
+            .collect();
+        groups.sort_by(|a, b| b.cmp(a));
+
+        (groups, num_wildcards)
+    }
+
+    /// Finds the strongest hand type on the ladder the cards can form, as a ladder index.
+    pub fn classify(&self, cards: &[Card]) -> Option<usize> {
+        let (groups, num_wildcards) = self.groups(cards);
+        let group_sizes: Vec<u32> = groups.iter().map(|(size, _)| *size).collect();
+
+        self.ladder
+            .iter()
+            .rposition(|category| category.is_formed_by(&group_sizes, num_wildcards))
+    }
+
+    /// Picks the card each wildcard stands in for so the cards form the ladder's `category`.
+    /// Wildcards first make up the shortfall of each group the category needs, and any that
+    /// are left over join the largest group.
+    pub fn wildcard_substitutes(&self, cards: &[Card], category: usize) -> Vec<char> {
+        let (groups, num_wildcards) = self.groups(cards);
+        let mut unused_labels = self.ranks
+            .iter()
+            .rev()
+            .filter(|label| Some(**label) != self.wildcard)
+            .filter(|label| !groups.iter().any(|(_, rank)| self.ranks[*rank as usize] == **label));
+
+        let mut substitutes = vec![];
+        for (i, needed) in self.ladder[category].groups.iter().enumerate() {
+            let (size, label) = match groups.get(i) {
+                Some((size, rank)) => (*size, self.ranks[*rank as usize]),
+                None => (0, *unused_labels.next().unwrap_or(&'?')),
+            };
+            for _ in size..*needed {
+                substitutes.push(label);
+            }
+        }
+
+        let fallback_label = groups
+            .first()
+            .map(|(_, rank)| self.ranks[*rank as usize])
+            .or(substitutes.first().copied())
+            .or(unused_labels.next().copied())
+            .unwrap_or('?');
+        substitutes.resize(num_wildcards as usize, fallback_label);
+        substitutes
+    }
+}
+
+#[derive(PartialOrd, PartialEq, Eq, Ord, Hash, Debug, Clone, Copy)]
+pub struct Card {
+    pub rank: u8,
+    pub label: char,
+}
+
+/// Hands order by hand type first and then card by card, which is exactly the field order
+/// here. `category` is the hand type's position on the ladder.
+#[derive(PartialOrd, PartialEq, Eq, Ord, Debug)]
+pub struct Hand {
+    pub category: usize,
+    pub cards: Vec<Card>,
+    pub bid_amount: u32,
+}
+
+impl Hand {
+    pub fn parse(text: &str, rules: &Rules) -> Result<Self, HandError> {
+        let mut parts = text.split_whitespace();
+
+        let cards_text = parts.next().ok_or(HandError::InvalidLine(text.to_string()))?;
+        let bid_text = parts.next().ok_or(HandError::InvalidLine(text.to_string()))?;
+        if parts.next().is_some() {
+            return Err(HandError::InvalidLine(text.to_string()));
+        }
+
+        let cards: Vec<Card> = cards_text
+            .chars()
+            .map(|label| rules.card(label))
+            .collect::<Result<_, _>>()?;
+        if cards.len() != rules.hand_size {
+            return Err(HandError::WrongSize {
+                cards: cards_text.to_string(),
+                expected: rules.hand_size,
+                actual: cards.len(),
+            });
+        }
+
+        Ok(Self {
+            category: rules.classify(&cards).ok_or(HandError::Unclassified(cards_text.to_string()))?,
+            cards,
+            bid_amount: bid_text.parse().map_err(|_| HandError::InvalidBid(bid_text.to_string()))?,
+        })
+    }
+
+    pub fn category_name<'a>(&self, rules: &'a Rules) -> &'a str {
+        &rules.ladder[self.category].name
+    }
+}
+
+/// Why a hand scored what it did: its type, the cards any wildcards stood in for, and its
+/// final rank among all the hands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandExplanation {
+    pub cards: String,
+    pub category: String,
+    pub wildcards_as: Vec<char>,
+    pub rank: u64,
+    pub bid_amount: u32,
+}
+
+impl HandExplanation {
+    pub fn winnings(&self) -> u64 {
+        self.rank * self.bid_amount as u64
+    }
+}
+
+impl fmt::Display for HandExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.cards, self.category)?;
+        if !self.wildcards_as.is_empty() {
+            let wildcards_as: Vec<String> = self.wildcards_as.iter().map(|label| label.to_string()).collect();
+            write!(f, " (wildcards as {})", wildcards_as.join(", "))?;
+        }
+        write!(f, ": rank {} * bid {} = {}", self.rank, self.bid_amount, self.winnings())
+    }
+}
+
+fn parse_hands(input: &str, rules: &Rules) -> Result<Vec<Hand>, HandError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Hand::parse(line, rules))
+        .collect()
+}
+
+/// Summed in `u128`, since a million hands with bids near `u32::MAX` overflow a `u64`.
+pub fn total_winnings(mut hands: Vec<Hand>) -> u128 {
+    hands.sort();
+
+    hands
+        .iter()
+        .enumerate()
+        .map(|(rank, hand)| (rank as u128 + 1) * hand.bid_amount as u128)
+        .sum()
+}
+
+pub fn process(input: &str, rules: &Rules) -> Result<u128, HandError> {
+    Ok(total_winnings(parse_hands(input, rules)?))
+}
+
+/// Explains every hand in rank order, weakest first.
+pub fn explain(input: &str, rules: &Rules) -> Result<Vec<HandExplanation>, HandError> {
+    let mut hands = parse_hands(input, rules)?;
+    hands.sort();
+
+    Ok(hands
+        .iter()
+        .enumerate()
+        .map(|(rank, hand)| HandExplanation {
+            cards: hand.cards.iter().map(|card| card.label).collect(),
+            category: hand.category_name(rules).to_string(),
+            wildcards_as: rules.wildcard_substitutes(&hand.cards, hand.category),
+            rank: rank as u64 + 1,
+            bid_amount: hand.bid_amount,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn category_name<'a>(rules: &'a Rules, cards_text: &str) -> Result<&'a str, HandError> {
+        let cards: Vec<Card> = cards_text.chars().map(|label| rules.card(label)).collect::<Result<_, _>>()?;
+        let category = rules.classify(&cards).ok_or(HandError::Unclassified(cards_text.to_string()))?;
+        Ok(&rules.ladder()[category].name)
+    }
+
+    #[rstest]
+    #[case("AAAAA", "five of a kind")]
+    #[case("AA8AA", "four of a kind")]
+    #[case("23332", "full house")]
+    #[case("TTT98", "three of a kind")]
+    #[case("23432", "two pair")]
+    #[case("A23A4", "one pair")]
+    #[case("23456", "high card")]
+    fn test_classify_without_wildcards(#[case] cards_text: &str, #[case] expected: &str) -> Result<(), HandError> {
+        assert_eq!(category_name(&Rules::standard(), cards_text)?, expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("JJJJJ", "five of a kind")]
+    #[case("JJJJ2", "five of a kind")]
+    #[case("2233J", "full house")]
+    #[case("234JJ", "three of a kind")]
+    #[case("2345J", "one pair")]
+    fn test_wildcard_rank_is_configurable(#[case] cards_text: &str, #[case] expected: &str) -> Result<(), HandError> {
+        let rules = Rules::new("23456789TJQKA", Some('J'))?;
+        assert_eq!(category_name(&rules, cards_text)?, expected);
+        Ok(())
+    }
+
+    /// Tries every non-joker rank in place of each joker and keeps the best plain hand type.
+    fn best_substitution(cards: &mut [Card; 5], plain_rules: &Rules) -> usize {
+        match cards.iter().position(|card| card.label == 'J') {
+            None => plain_rules.classify(cards).expect("the standard ladder covers every hand"),
+            Some(joker_index) => {
+                let mut best = 0;
+                for label in "23456789TQKA".chars() {
+                    cards[joker_index] = plain_rules.card(label).expect("label is a standard card");
+                    best = best.max(best_substitution(cards, plain_rules));
+                }
+                cards[joker_index] = plain_rules.card('J').expect("J is a standard card");
+                best
+            }
+        }
+    }
+
+    #[test]
+    fn test_jokers_match_brute_force_substitution() {
+        let joker_rules = Rules::jokers();
+        let plain_rules = Rules::standard();
+        let labels: Vec<char> = "23456789TJQKA".chars().collect();
+
+        for hand_index in 0..labels.len().pow(5) {
+            let mut remaining = hand_index;
+            let mut hand_labels = ['2'; 5];
+            for label in hand_labels.iter_mut() {
+                *label = labels[remaining % labels.len()];
+                remaining /= labels.len();
+            }
+
+            let joker_cards = hand_labels.map(|label| joker_rules.card(label).expect("label is a card"));
+            let mut plain_cards = hand_labels.map(|label| plain_rules.card(label).expect("label is a card"));
+
+            assert_eq!(
+                joker_rules.classify(&joker_cards),
+                Some(best_substitution(&mut plain_cards, &plain_rules)),
+                "hand {}", hand_labels.iter().collect::<String>()
+            );
+        }
+    }
+
+    fn seven_card_rules() -> Result<Rules, HandError> {
+        Rules::new("*23456789TJQKA", Some('*'))?
+            .with_hand_size(7)
+            .with_ladder(vec![
+                HandCategory::new("high card", &[]),
+                HandCategory::new("one pair", &[2]),
+                HandCategory::new("two pair", &[2, 2]),
+                HandCategory::new("three pair", &[2, 2, 2]),
+                HandCategory::new("three of a kind", &[3]),
+                HandCategory::new("full house", &[3, 2]),
+                HandCategory::new("four of a kind", &[4]),
+                HandCategory::new("seven of a kind", &[7]),
+            ])
+    }
+
+    #[rstest]
+    #[case("2233449", "three pair")]
+    #[case("223344*", "full house")]
+    #[case("2233456", "two pair")]
+    #[case("2345678", "high card")]
+    #[case("******A", "seven of a kind")]
+    fn test_seven_card_ladder(#[case] cards_text: &str, #[case] expected: &str) -> Result<(), HandError> {
+        assert_eq!(category_name(&seven_card_rules()?, cards_text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_five_pair_beats_greedy_wildcards() -> Result<(), HandError> {
+        // putting the wildcard on the biggest group would only give a three of a kind here
+        let rules = Rules::new("23456789TJQKA", Some('J'))?
+            .with_hand_size(10)
+            .with_ladder(vec![
+                HandCategory::new("high card", &[]),
+                HandCategory::new("three of a kind", &[3]),
+                HandCategory::new("five pair", &[2, 2, 2, 2, 2]),
+            ])?;
+        assert_eq!(category_name(&rules, "223344556J")?, "five pair");
+        Ok(())
+    }
+
+    #[test]
+    fn test_total_winnings_with_seven_cards() -> Result<(), HandError> {
+        let input = "\
+2233449 10
+223344* 20
+2345678 30";
+        assert_eq!(process(input, &seven_card_rules()?)?, 30 + 10 * 2 + 20 * 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(matches!(Rules::new("23452", None), Err(HandError::DuplicateRank('2'))));
+        assert!(matches!(Rules::new("2345", Some('J')), Err(HandError::UnknownWildcard('J'))));
+        assert!(matches!(Rules::standard().with_ladder(vec![]), Err(HandError::EmptyLadder)));
+    }
+
+    #[test]
+    fn test_unclassified_hand() -> Result<(), HandError> {
+        let rules = Rules::standard().with_ladder(vec![HandCategory::new("one pair", &[2])])?;
+        assert!(matches!(Hand::parse("23456 1", &rules), Err(HandError::Unclassified(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain() -> Result<(), HandError> {
+        let input = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+        let explanations = explain(input, &Rules::jokers())?;
+        let lines: Vec<String> = explanations.iter().map(|explanation| explanation.to_string()).collect();
+
+        assert_eq!(lines, vec![
+            "32T3K one pair: rank 1 * bid 765 = 765",
+            "KK677 two pair: rank 2 * bid 28 = 56",
+            "T55J5 four of a kind (wildcards as 5): rank 3 * bid 684 = 2052",
+            "QQQJA four of a kind (wildcards as Q): rank 4 * bid 483 = 1932",
+            "KTJJT four of a kind (wildcards as T, T): rank 5 * bid 220 = 1100",
+        ]);
+        assert_eq!(explanations.iter().map(|explanation| explanation.winnings()).sum::<u64>(), 5905);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("JJJJJ", vec!['A', 'A', 'A', 'A', 'A'])]
+    #[case("2345J", vec!['5'])]
+    #[case("2233J", vec!['3'])]
+    fn test_wildcard_substitutes(#[case] cards_text: &str, #[case] expected: Vec<char>) -> Result<(), HandError> {
+        let rules = Rules::jokers();
+        let cards: Vec<Card> = cards_text.chars().map(|label| rules.card(label)).collect::<Result<_, _>>()?;
+        let category = rules.classify(&cards).ok_or(HandError::Unclassified(cards_text.to_string()))?;
+        assert_eq!(rules.wildcard_substitutes(&cards, category), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_wildcard_substitutes_split_across_groups() -> Result<(), HandError> {
+        let rules = Rules::new("23456789TJQKA", Some('J'))?
+            .with_hand_size(10)
+            .with_ladder(vec![
+                HandCategory::new("high card", &[]),
+                HandCategory::new("five pair", &[2, 2, 2, 2, 2]),
+            ])?;
+        let cards: Vec<Card> = "2233445JJJ".chars().map(|label| rules.card(label)).collect::<Result<_, _>>()?;
+        assert_eq!(rules.wildcard_substitutes(&cards, 1), vec!['5', 'A', 'A']);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("32T3 765")]
+    #[case("32T3KK 765")]
+    #[case("32T3X 765")]
+    #[case("32T3K")]
+    #[case("32T3K abc")]
+    fn test_parse_invalid_hand(#[case] text: &str) {
+        assert!(Hand::parse(text, &Rules::standard()).is_err());
+    }
+}
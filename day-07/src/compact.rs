@@ -0,0 +1,234 @@
+use crate::camel::{HandError, Rules};
+
+const NO_RANK: u8 = u8::MAX;
+
+/// Encodes hands as single integers that sort the same way as `Hand`: the hand type's ladder
+/// index in the top nibble, then one nibble per card rank, first card most significant. A
+/// 5-card hand uses 24 bits, and nothing is allocated per hand.
+pub struct KeyEncoder<'a> {
+    rules: &'a Rules,
+    rank_of: [u8; 256],
+    wildcard_rank: u8,
+}
+
+impl<'a> KeyEncoder<'a> {
+    /// Only rules with at most 16 ASCII ranks, 16 hand types and 7 cards fit in a `u32` key.
+    pub fn new(rules: &'a Rules) -> Result<Self, HandError> {
+        if rules.ranks().len() > 16 || rules.ladder().len() > 16 || rules.hand_size() > 7 {
+            return Err(HandError::UnsupportedRules(
+                "compact keys need at most 16 ranks, 16 hand types and 7 cards".to_string(),
+            ));
+        }
+
+        let mut rank_of = [NO_RANK; 256];
+        for (rank, label) in rules.ranks().iter().enumerate() {
+            if !label.is_ascii() {
+                return Err(HandError::UnsupportedRules(format!("card `{}` is not ASCII", label)));
+            }
+            rank_of[*label as usize] = rank as u8;
+        }
+
+        let wildcard_rank = rules
+            .wildcard()
+            .map_or(NO_RANK, |wildcard| rank_of[wildcard as usize]);
+
+        Ok(Self { rules, rank_of, wildcard_rank })
+    }
+
+    pub fn key(&self, cards_text: &str) -> Result<u32, HandError> {
+        let cards = cards_text.as_bytes();
+        if cards.len() != self.rules.hand_size() {
+            return Err(HandError::WrongSize {
+                cards: cards_text.to_string(),
+                expected: self.rules.hand_size(),
+                actual: cards_text.chars().count(),
+            });
+        }
+
+        let mut counts = [0u32; 16];
+        let mut num_wildcards = 0;
+        let mut card_nibbles = 0u32;
+        for card in cards {
+            let rank = self.rank_of[*card as usize];
+            if rank == NO_RANK {
+                return Err(HandError::UnknownCard(*card as char));
+            }
+
+            card_nibbles = card_nibbles << 4 | rank as u32;
+            if rank == self.wildcard_rank {
+                num_wildcards += 1;
+            } else {
+                counts[rank as usize] += 1;
+            }
+        }
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        let category = self.rules
+            .ladder()
+            .iter()
+            .rposition(|category| category.is_formed_by(&counts, num_wildcards))
+            .ok_or(HandError::Unclassified(cards_text.to_string()))?;
+
+        Ok((category as u32) << (4 * cards.len()) | card_nibbles)
+    }
+
+    /// Packs a `cards bid` line as `key << 32 | bid`, so sorting the packed values sorts the
+    /// hands while keeping each bid alongside.
+    pub fn packed_line(&self, line: &str) -> Result<u64, HandError> {
+        let mut parts = line.split_whitespace();
+
+        let cards_text = parts.next().ok_or(HandError::InvalidLine(line.to_string()))?;
+        let bid_text = parts.next().ok_or(HandError::InvalidLine(line.to_string()))?;
+        if parts.next().is_some() {
+            return Err(HandError::InvalidLine(line.to_string()));
+        }
+        let bid_amount: u32 = bid_text
+            .parse()
+            .map_err(|_| HandError::InvalidBid(bid_text.to_string()))?;
+
+        Ok((self.key(cards_text)? as u64) << 32 | bid_amount as u64)
+    }
+}
+
+/// Same result as `camel::process`, but sorts packed integers instead of `Hand`s.
+pub fn total_winnings(input: &str, rules: &Rules) -> Result<u128, HandError> {
+    let encoder = KeyEncoder::new(rules)?;
+
+    let mut packed_hands = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| encoder.packed_line(line))
+        .collect::<Result<Vec<u64>, HandError>>()?;
+    packed_hands.sort_unstable();
+
+    Ok(packed_hands
+        .iter()
+        .enumerate()
+        .map(|(rank, packed)| (rank as u128 + 1) * (packed & u32::MAX as u64) as u128)
+        .sum())
+}
+
+/// Marsaglia's xorshift64 step. The state must never be zero, or it stays zero.
+fn xorshift64(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Generates `count` random `cards bid` lines for the rules, reproducibly from `seed`.
+pub fn generate_hands(count: usize, seed: u64, rules: &Rules) -> String {
+    let mut state = seed | 1;
+
+    let ranks = rules.ranks();
+    let mut hands = String::with_capacity(count * (rules.hand_size() + 6));
+    for _ in 0..count {
+        for _ in 0..rules.hand_size() {
+            hands.push(ranks[xorshift64(&mut state) as usize % ranks.len()]);
+        }
+        hands.push(' ');
+        hands.push_str(&(xorshift64(&mut state) % 1000 + 1).to_string());
+        hands.push('\n');
+    }
+    hands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camel::{self, Hand};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Rules::standard())]
+    #[case(Rules::jokers())]
+    fn test_keys_sort_like_hands(#[case] rules: Rules) -> Result<(), HandError> {
+        let encoder = KeyEncoder::new(&rules)?;
+        let input = generate_hands(2000, 7, &rules);
+
+        let mut hands: Vec<(u32, Hand)> = input
+            .lines()
+            .map(|line| Ok((encoder.key(&line[..5])?, Hand::parse(line, &rules)?)))
+            .collect::<Result<_, HandError>>()?;
+        hands.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+        for window in hands.windows(2) {
+            let ((key_a, hand_a), (key_b, hand_b)) = (&window[0], &window[1]);
+            let hand_order = (hand_a.category, &hand_a.cards).cmp(&(hand_b.category, &hand_b.cards));
+            assert_eq!(key_a.cmp(key_b), hand_order);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_layout() -> Result<(), HandError> {
+        let rules = Rules::standard();
+        let encoder = KeyEncoder::new(&rules)?;
+        // two pair is ladder index 2; K=11, 6=4, 7=5
+        assert_eq!(encoder.key("KK677")?, 0x2BB455);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Rules::standard(), 6440)]
+    #[case(Rules::jokers(), 5905)]
+    fn test_total_winnings(#[case] rules: Rules, #[case] expected: u128) -> Result<(), HandError> {
+        let input = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+        assert_eq!(total_winnings(input, &rules)?, expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Rules::standard())]
+    #[case(Rules::jokers())]
+    fn test_total_winnings_matches_hand_sort(#[case] rules: Rules) -> Result<(), HandError> {
+        let input = generate_hands(20_000, 42, &rules);
+        assert_eq!(total_winnings(&input, &rules)?, camel::process(&input, &rules)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_total_winnings_beyond_u64() -> Result<(), HandError> {
+        // with every bid at u32::MAX, 100,000 hands already win more than u64::MAX
+        let rules = Rules::standard();
+        let count = 100_000u128;
+        let input = generate_hands(count as usize, 7, &rules)
+            .lines()
+            .map(|line| format!("{} {}", line.split_once(' ').expect("a generated line has a bid").0, u32::MAX))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let expected = u32::MAX as u128 * count * (count + 1) / 2;
+        assert!(expected > u64::MAX as u128);
+        assert_eq!(total_winnings(&input, &rules)?, expected);
+        assert_eq!(camel::process(&input, &rules)?, expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(" ", "\t")]
+    #[case(" ", "   ")]
+    #[case("\n", "  \n\t")]
+    fn test_total_winnings_whitespace(#[case] from: &str, #[case] to: &str) -> Result<(), HandError> {
+        let input = "\
+32T3K 765
+T55J5 684
+KK677 28".replace(from, to);
+
+        for rules in [Rules::standard(), Rules::jokers()] {
+            assert_eq!(total_winnings(&input, &rules)?, camel::process(&input, &rules)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_rules() {
+        let rules = Rules::standard().with_hand_size(8);
+        assert!(matches!(KeyEncoder::new(&rules), Err(HandError::UnsupportedRules(_))));
+    }
+}
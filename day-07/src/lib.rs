@@ -1,2 +1,4 @@
+pub mod camel;
+pub mod compact;
 pub mod part1;
 pub mod part2;
\ No newline at end of file
@@ -0,0 +1,131 @@
+use std::fmt;
+use crate::ghosts::GhostCycle;
+use crate::network::{Network, NodeId};
+
+/// What one start location's walk looks like when following the instructions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartReport {
+    pub start: String,
+    pub ends_reached: Vec<String>,
+    pub first_end_step: Option<u64>,
+    pub cycle_start: u64,
+    pub cycle_len: u64,
+}
+
+/// A structural overview of a network: locations no start can get to whatever instructions
+/// it follows, sinks that only lead back to themselves, and which end locations each start's
+/// walk actually lands on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkReport {
+    pub num_locations: usize,
+    pub unreachable: Vec<String>,
+    pub sinks: Vec<String>,
+    pub starts: Vec<StartReport>,
+}
+
+/// Marks every location reachable from `starts` by any mix of left and right moves.
+fn reachable(network: &Network, starts: &[NodeId]) -> Vec<bool> {
+    let mut reached = vec![false; network.len()];
+    let mut to_visit = starts.to_vec();
+
+    while let Some(node) = to_visit.pop() {
+        if reached[node as usize] {
+            continue;
+        }
+        reached[node as usize] = true;
+        to_visit.push(network.left(node));
+        to_visit.push(network.right(node));
+    }
+
+    reached
+}
+
+pub fn analyze(network: &Network, is_start: impl Fn(&str) -> bool, is_end: impl Fn(&str) -> bool) -> NetworkReport {
+    let starts: Vec<NodeId> = network.ids().filter(|&node| is_start(network.label(node))).collect();
+    let reached = reachable(network, &starts);
+
+    let unreachable = network
+        .ids()
+        .filter(|&node| !reached[node as usize])
+        .map(|node| network.label(node).to_string())
+        .collect();
+
+    let sinks = network
+        .ids()
+        .filter(|&node| network.left(node) == node && network.right(node) == node)
+        .map(|node| network.label(node).to_string())
+        .collect();
+
+    let starts = starts
+        .iter()
+        .map(|&start| {
+            let ghost = GhostCycle::find(network, start, &is_end);
+            StartReport {
+                start: network.label(start).to_string(),
+                ends_reached: ghost.ends_visited
+                    .iter()
+                    .map(|&end| network.label(end).to_string())
+                    .collect(),
+                first_end_step: ghost.first_hit(),
+                cycle_start: ghost.cycle_start,
+                cycle_len: ghost.cycle_len,
+            }
+        })
+        .collect();
+
+    NetworkReport {
+        num_locations: network.len(),
+        unreachable,
+        sinks,
+        starts,
+    }
+}
+
+impl fmt::Display for NetworkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "locations: {}", self.num_locations)?;
+        writeln!(f, "unreachable from any start ({}): {}", self.unreachable.len(), self.unreachable.join(" "))?;
+        writeln!(f, "self-loop sinks ({}): {}", self.sinks.len(), self.sinks.join(" "))?;
+
+        for start in &self.starts {
+            write!(f, "{}: loops every {} steps after {}", start.start, start.cycle_len, start.cycle_start)?;
+            match start.first_end_step {
+                Some(step) => writeln!(f, ", first end at step {}, reaches {}", step, start.ends_reached.join(" "))?,
+                None => writeln!(f, ", never reaches an end")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ProcessError;
+
+    #[test]
+    fn test_analyze() -> Result<(), ProcessError> {
+        let network = Network::parse("\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+33A = (XXX, XXX)
+44B = (22Z, 33Z)
+33Z = (33Z, 33Z)
+XXX = (XXX, XXX)")?;
+        let report = analyze(&network, |location| location.ends_with('A'), |location| location.ends_with('Z'));
+
+        assert_eq!(report.unreachable, ["44B", "33Z"]);
+        assert_eq!(report.sinks, ["33Z", "XXX"]);
+        assert_eq!(report.starts.iter().map(|start| start.first_end_step).collect::<Vec<_>>(), [Some(2), Some(3), None]);
+        assert_eq!(report.starts[1].ends_reached, ["22Z"]);
+        assert_eq!(report.to_string().lines().last(), Some("33A: loops every 2 steps after 1, never reaches an end"));
+        Ok(())
+    }
+}
\ No newline at end of file
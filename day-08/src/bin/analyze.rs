@@ -0,0 +1,20 @@
+use std::fs;
+use anyhow::{bail, Result};
+use day_08::analysis::analyze;
+use day_08::network::Network;
+
+/// Reports unreachable locations, self-loop sinks and which `..Z` ends each `..A` start
+/// reaches. Run as `analyze [path]`.
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let input = match &args[..] {
+        [] => include_str!("../../input2.txt").to_string(),
+        [path] => fs::read_to_string(path)?,
+        _ => bail!("usage: analyze [path]"),
+    };
+
+    let network = Network::parse(&input)?;
+    print!("{}", analyze(&network, |location| location.ends_with('A'), |location| location.ends_with('Z')));
+    Ok(())
+}
\ No newline at end of file
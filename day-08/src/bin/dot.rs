@@ -0,0 +1,27 @@
+use std::fs;
+use anyhow::{bail, Result};
+use day_08::dot::to_dot;
+use day_08::ghosts::GhostCycle;
+use day_08::network::Network;
+
+/// Prints the network as Graphviz DOT, with each ghost's loop in its own color.
+/// Run as `dot [path] | dot -Tsvg > network.svg`.
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let input = match &args[..] {
+        [] => include_str!("../../input2.txt").to_string(),
+        [path] => fs::read_to_string(path)?,
+        _ => bail!("usage: dot [path]"),
+    };
+
+    let network = Network::parse(&input)?;
+    let ghosts: Vec<GhostCycle> = network
+        .ids()
+        .filter(|&node| network.label(node).ends_with('A'))
+        .map(|start| GhostCycle::find(&network, start, |location| location.ends_with('Z')))
+        .collect();
+
+    print!("{}", to_dot(&network, &ghosts));
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+use std::fs;
+use anyhow::{bail, Result};
+use day_08::navigate::{steps, LocationRule};
+use day_08::network::Network;
+
+/// Counts the steps until ghosts on every start location all stand on a goal location.
+/// Run as `navigate <start-rule> <goal-rule> [path]`, where a rule is `exact:AAA`,
+/// `prefix:11`, `suffix:Z`, `regex:^1.A$` or `set:AAA,BBB`.
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let (start, goal, input) = match &args[..] {
+        [start, goal] => (start, goal, include_str!("../../input2.txt").to_string()),
+        [start, goal, path] => (start, goal, fs::read_to_string(path)?),
+        _ => bail!("usage: navigate <start-rule> <goal-rule> [path]"),
+    };
+
+    let network = Network::parse(&input)?;
+    let result = steps(&network, &LocationRule::parse(start)?, &LocationRule::parse(goal)?)?;
+    println!("{}", result);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use crate::ghosts::GhostCycle;
+use crate::network::{Network, NodeId};
+
+const CYCLE_COLORS: [&str; 8] = [
+    "blue", "darkorange", "purple", "darkgreen", "red", "saddlebrown", "deeppink", "teal",
+];
+
+/// Renders the network as a Graphviz digraph. Start locations (`..A`) are filled green and
+/// end locations (`..Z`) red, edges are labeled with the instruction that follows them, and
+/// the loop each ghost settles into is drawn bold in its own color. An edge shared by several
+/// loops takes the color of the first ghost.
+pub fn to_dot(network: &Network, ghosts: &[GhostCycle]) -> String {
+    let mut cycle_color: HashMap<(NodeId, bool), &str> = HashMap::new();
+    for (ghost, color) in ghosts.iter().zip(CYCLE_COLORS.iter().cycle()) {
+        for edge in ghost.cycle_edges(network) {
+            cycle_color.entry(edge).or_insert(color);
+        }
+    }
+
+    let mut dot = String::new();
+    writeln!(dot, "digraph network {{").unwrap();
+    writeln!(dot, "    node [shape=ellipse];").unwrap();
+
+    for node in network.ids() {
+        let label = network.label(node);
+        if label.ends_with('A') {
+            writeln!(dot, "    \"{}\" [style=filled, fillcolor=palegreen];", label).unwrap();
+        } else if label.ends_with('Z') {
+            writeln!(dot, "    \"{}\" [style=filled, fillcolor=lightcoral];", label).unwrap();
+        }
+    }
+
+    for node in network.ids() {
+        let (left, right) = (network.left(node), network.right(node));
+        // one edge for both instructions when they lead to the same place
+        let edges: Vec<(NodeId, &str, Option<&str>)> = if left == right {
+            let color = cycle_color.get(&(node, false)).or(cycle_color.get(&(node, true)));
+            vec![(left, "L/R", color.copied())]
+        } else {
+            vec![
+                (left, "L", cycle_color.get(&(node, false)).copied()),
+                (right, "R", cycle_color.get(&(node, true)).copied()),
+            ]
+        };
+
+        for (dest, instruction, color) in edges {
+            write!(dot, "    \"{}\" -> \"{}\" [label=\"{}\"", network.label(node), network.label(dest), instruction).unwrap();
+            if let Some(color) = color {
+                write!(dot, ", color={}, fontcolor={}, penwidth=2.5", color, color).unwrap();
+            }
+            writeln!(dot, "];").unwrap();
+        }
+    }
+
+    writeln!(dot, "}}").unwrap();
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ProcessError;
+
+    #[test]
+    fn test_to_dot() -> Result<(), ProcessError> {
+        let network = Network::parse("\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+XXX = (XXX, XXX)")?;
+        let ghosts = vec![GhostCycle::find(&network, network.id("11A")?, |location| location.ends_with('Z'))];
+
+        assert_eq!(to_dot(&network, &ghosts), "\
+digraph network {
+    node [shape=ellipse];
+    \"11A\" [style=filled, fillcolor=palegreen];
+    \"11Z\" [style=filled, fillcolor=lightcoral];
+    \"11A\" -> \"11B\" [label=\"L\"];
+    \"11A\" -> \"XXX\" [label=\"R\"];
+    \"11B\" -> \"XXX\" [label=\"L\"];
+    \"11B\" -> \"11Z\" [label=\"R\", color=blue, fontcolor=blue, penwidth=2.5];
+    \"11Z\" -> \"11B\" [label=\"L\", color=blue, fontcolor=blue, penwidth=2.5];
+    \"11Z\" -> \"XXX\" [label=\"R\"];
+    \"XXX\" -> \"XXX\" [label=\"L/R\"];
+}
+");
+        Ok(())
+    }
+}
\ No newline at end of file
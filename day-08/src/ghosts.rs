@@ -0,0 +1,353 @@
+use crate::network::{Network, NodeId, ProcessError};
+
+/// Every step at which one ghost stands on an end location. A ghost's walk is fully
+/// determined by its (location, instruction index) state, so once a state repeats the walk
+/// loops: `transient_hits` happen once before the loop starts at `cycle_start`, and each of
+/// `cycle_hits` recurs every `cycle_len` steps. `ends_visited` lists the distinct end
+/// locations behind those hits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    pub start: NodeId,
+    pub ends_visited: Vec<NodeId>,
+    pub transient_hits: Vec<u64>,
+    pub cycle_hits: Vec<u64>,
+    pub cycle_start: u64,
+    pub cycle_len: u64,
+}
+
+impl GhostCycle {
+    /// Walks from `start` until a state repeats, recording the steps that land on an end.
+    pub fn find(network: &Network, start: NodeId, is_end: impl Fn(&str) -> bool) -> Self {
+        let num_instructions = network.num_instructions();
+        let ends: Vec<bool> = network.ids().map(|node| is_end(network.label(node))).collect();
+
+        // step at which each (location, instruction index) state was first seen
+        let mut seen = vec![u64::MAX; network.len() * num_instructions];
+        let state = |node: NodeId, instruction_index: usize| node as usize * num_instructions + instruction_index;
+        let mut hits = vec![];
+        let mut ends_visited = vec![];
+
+        let mut current_location = start;
+        let mut instruction_index = 0;
+        let mut num_moves: u64 = 0;
+        while seen[state(current_location, instruction_index)] == u64::MAX {
+            seen[state(current_location, instruction_index)] = num_moves;
+            if ends[current_location as usize] {
+                hits.push(num_moves);
+                ends_visited.push(current_location);
+            }
+
+            current_location = network.step(current_location, instruction_index);
+            instruction_index = (instruction_index + 1) % num_instructions;
+            num_moves += 1;
+        }
+
+        let cycle_start = seen[state(current_location, instruction_index)];
+        let (transient_hits, cycle_hits) = hits.into_iter().partition(|&hit| hit < cycle_start);
+        ends_visited.sort_unstable();
+        ends_visited.dedup();
+
+        Self {
+            start,
+            ends_visited,
+            transient_hits,
+            cycle_hits,
+            cycle_start,
+            cycle_len: num_moves - cycle_start,
+        }
+    }
+
+    /// The first step, after at least one move, that lands on an end.
+    pub fn first_hit(&self) -> Option<u64> {
+        self.progressions()
+            .iter()
+            .filter_map(|progression| progression.first_move())
+            .min()
+    }
+
+    /// The (location, went right) edges the ghost keeps following once it is in its loop.
+    pub fn cycle_edges(&self, network: &Network) -> Vec<(NodeId, bool)> {
+        let num_instructions = network.num_instructions();
+        let mut current_location = self.start;
+        let mut edges = vec![];
+
+        for num_moves in 0..self.cycle_start + self.cycle_len {
+            let instruction_index = (num_moves % num_instructions as u64) as usize;
+            if num_moves >= self.cycle_start {
+                edges.push((current_location, network.goes_right(instruction_index)));
+            }
+            current_location = network.step(current_location, instruction_index);
+        }
+
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    }
+
+    /// The usual shape of puzzle inputs: the only end on the loop is reached exactly one
+    /// loop length in, so the ghost is on an end at every multiple of `cycle_len`.
+    fn is_simple(&self) -> bool {
+        self.transient_hits.is_empty() && self.cycle_hits == [self.cycle_len]
+    }
+
+    fn progressions(&self) -> Vec<Progression> {
+        self.transient_hits
+            .iter()
+            .map(|&hit| Progression { first: hit, period: 0 })
+            .chain(self.cycle_hits.iter().map(|&hit| Progression { first: hit, period: self.cycle_len }))
+            .collect()
+    }
+}
+
+/// The steps `first, first + period, first + 2 * period, ...`, or only `first` when the
+/// period is zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Progression {
+    first: u64,
+    period: u64,
+}
+
+impl Progression {
+    fn contains(&self, step: u64) -> bool {
+        if self.period == 0 {
+            step == self.first
+        } else {
+            step >= self.first && (step - self.first).is_multiple_of(self.period)
+        }
+    }
+
+    /// The steps in both progressions, merged with the Chinese remainder theorem when both
+    /// repeat, or `None` if they never meet.
+    fn intersect(&self, other: &Progression) -> Result<Option<Progression>, ProcessError> {
+        if self.period == 0 {
+            return Ok(other.contains(self.first).then_some(*self));
+        }
+        if other.period == 0 {
+            return Ok(self.contains(other.first).then_some(*other));
+        }
+
+        let (a, p) = (self.first as i128, self.period as i128);
+        let (b, q) = (other.first as i128, other.period as i128);
+        let (g, p_inverse, _) = extended_gcd(p, q);
+        if (b - a) % g != 0 {
+            return Ok(None);
+        }
+
+        // a + p * k ≡ b (mod q)  =>  k ≡ (b - a) / g * p⁻¹ (mod q / g)
+        let q_reduced = q / g;
+        let k = ((b - a) / g % q_reduced * (p_inverse % q_reduced)).rem_euclid(q_reduced);
+        let period = p * q_reduced;
+        let lowest = a.max(b);
+        let mut first = (a + p * k).rem_euclid(period);
+        if first < lowest {
+            first += (lowest - first + period - 1) / period * period;
+        }
+
+        let first = u64::try_from(first).map_err(|_| ProcessError::StepOverflow)?;
+        let period = u64::try_from(period).map_err(|_| ProcessError::StepOverflow)?;
+        Ok(Some(Progression { first, period }))
+    }
+
+    /// The first step that involves at least one move.
+    fn first_move(&self) -> Option<u64> {
+        match (self.first, self.period) {
+            (0, 0) => None,
+            (0, period) => Some(period),
+            (first, _) => Some(first),
+        }
+    }
+}
+
+/// Returns `(g, x, y)` with `p * x + q * y == g == gcd(p, q)`.
+fn extended_gcd(p: i128, q: i128) -> (i128, i128, i128) {
+    if q == 0 {
+        (p, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(q, p % q);
+        (g, y, x - p / q * y)
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+fn lcm(a: u64, b: u64) -> Result<u64, ProcessError> {
+    (a / gcd(a, b))
+        .checked_mul(b)
+        .ok_or(ProcessError::StepOverflow)
+}
+
+/// The first step, after at least one move, at which every ghost stands on an end at once.
+pub fn first_common_step(ghosts: &[GhostCycle]) -> Result<u64, ProcessError> {
+    if ghosts.is_empty() {
+        return Err(ProcessError::NoStartLocations);
+    }
+
+    if ghosts.iter().all(|ghost| ghost.is_simple()) {
+        return ghosts
+            .iter()
+            .try_fold(1, |steps, ghost| lcm(steps, ghost.cycle_len));
+    }
+
+    let mut common = ghosts[0].progressions();
+    for ghost in &ghosts[1..] {
+        let mut merged = vec![];
+        for progression in &common {
+            for other in ghost.progressions() {
+                if let Some(both) = progression.intersect(&other)? {
+                    merged.push(both);
+                }
+            }
+        }
+        common = merged;
+    }
+
+    common
+        .iter()
+        .filter_map(|progression| progression.first_move())
+        .min()
+        .ok_or(ProcessError::NoCommonStep)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn ghosts(input: &str) -> Result<Vec<GhostCycle>, ProcessError> {
+        let network = Network::parse(input)?;
+        Ok(network
+            .ids()
+            .filter(|&node| network.label(node).ends_with('A'))
+            .map(|start| GhostCycle::find(&network, start, |location| location.ends_with('Z')))
+            .collect())
+    }
+
+    /// Steps the ghosts in lockstep, for checking small networks.
+    fn brute_force(input: &str, limit: u64) -> Result<Option<u64>, ProcessError> {
+        let network = Network::parse(input)?;
+        let mut locations: Vec<NodeId> = network
+            .ids()
+            .filter(|&node| network.label(node).ends_with('A'))
+            .collect();
+
+        for (num_moves, instruction_index) in (1..=limit).zip((0..network.num_instructions()).cycle()) {
+            for location in locations.iter_mut() {
+                *location = network.step(*location, instruction_index);
+            }
+            if locations.iter().all(|&location| network.label(location).ends_with('Z')) {
+                return Ok(Some(num_moves));
+            }
+        }
+        Ok(None)
+    }
+
+    #[test]
+    fn test_find_cycle() -> Result<(), ProcessError> {
+        let input = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+XXX = (XXX, XXX)";
+        assert_eq!(ghosts(input)?, vec![GhostCycle {
+            start: 0,
+            ends_visited: vec![2],
+            transient_hits: vec![],
+            cycle_hits: vec![2],
+            cycle_start: 1,
+            cycle_len: 2,
+        }]);
+        Ok(())
+    }
+
+    #[rstest]
+    // both ghosts loop every 2 and 3 steps from the start: plain LCM
+    #[case("\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)", 6)]
+    // neither end falls on a multiple of its loop length: 3 (mod 4) against 5 (mod 6)
+    #[case("\
+L
+
+11A = (11B, 11B)
+11B = (11C, 11C)
+11C = (11Z, 11Z)
+11Z = (11D, 11D)
+11D = (11E, 11E)
+11E = (11F, 11F)
+11F = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22C, 22C)
+22C = (22D, 22D)
+22D = (22E, 22E)
+22E = (22Z, 22Z)
+22Z = (22A, 22A)", 11)]
+    // an end passed only once on the way into the loop
+    #[case("\
+L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11C, 11C)
+11C = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22Z, 22Z)", 1)]
+    // two ends on one loop
+    #[case("\
+L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (1YZ, 1YZ)
+1YZ = (11C, 11C)
+11C = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22A, 22A)", 3)]
+    fn test_first_common_step(#[case] input: &str, #[case] expected: u64) -> Result<(), ProcessError> {
+        assert_eq!(first_common_step(&ghosts(input)?)?, expected);
+        assert_eq!(brute_force(input, 1000)?, Some(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_common_step() -> Result<(), ProcessError> {
+        // ends on even steps only against ends on odd steps only
+        let input = "\
+L
+
+11A = (11Z, 11Z)
+11Z = (11A, 11A)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22C, 22C)
+22C = (22Z, 22Z)";
+        assert!(matches!(first_common_step(&ghosts(input)?), Err(ProcessError::NoCommonStep)));
+        assert_eq!(brute_force(input, 1000)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_intersect() -> Result<(), ProcessError> {
+        let a = Progression { first: 3, period: 4 };
+        let b = Progression { first: 0, period: 6 };
+        assert_eq!(a.intersect(&b)?, None);
+
+        let c = Progression { first: 9, period: 6 };
+        assert_eq!(a.intersect(&c)?, Some(Progression { first: 15, period: 12 }));
+        assert_eq!(a.intersect(&Progression { first: 11, period: 0 })?, Some(Progression { first: 11, period: 0 }));
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -1,2 +1,7 @@
+pub mod analysis;
+pub mod dot;
+pub mod ghosts;
+pub mod navigate;
+pub mod network;
 pub mod part1;
 pub mod part2;
\ No newline at end of file
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+use std::fmt;
+use regex::Regex;
+use crate::ghosts::{self, GhostCycle};
+use crate::network::{Network, NodeId, ProcessError};
+
+/// Which locations count as starts or goals.
+#[derive(Debug, Clone)]
+pub enum LocationRule {
+    Exact(String),
+    Prefix(String),
+    Suffix(String),
+    Regex(Regex),
+    Set(HashSet<String>),
+}
+
+impl LocationRule {
+    /// Parses `exact:AAA`, `prefix:11`, `suffix:Z`, `regex:^1.A$` or `set:AAA,BBB`.
+    pub fn parse(rule_text: &str) -> Result<Self, ProcessError> {
+        let (kind, pattern) = rule_text
+            .split_once(':')
+            .ok_or(ProcessError::InvalidRule(rule_text.to_string()))?;
+
+        Ok(match kind {
+            "exact" => Self::Exact(pattern.to_string()),
+            "prefix" => Self::Prefix(pattern.to_string()),
+            "suffix" => Self::Suffix(pattern.to_string()),
+            "regex" => Self::Regex(Regex::new(pattern)?),
+            "set" => Self::Set(pattern.split(',').map(|label| label.trim().to_string()).collect()),
+            _ => return Err(ProcessError::InvalidRule(rule_text.to_string())),
+        })
+    }
+
+    pub fn matches(&self, location: &str) -> bool {
+        match self {
+            Self::Exact(label) => location == label,
+            Self::Prefix(prefix) => location.starts_with(prefix.as_str()),
+            Self::Suffix(suffix) => location.ends_with(suffix.as_str()),
+            Self::Regex(regex) => regex.is_match(location),
+            Self::Set(labels) => labels.contains(location),
+        }
+    }
+
+    /// The matching locations, in the order they are listed. An exact label has to exist.
+    pub fn locations(&self, network: &Network) -> Result<Vec<NodeId>, ProcessError> {
+        if let Self::Exact(label) = self {
+            return Ok(vec![network.id(label)?]);
+        }
+
+        Ok(network.ids().filter(|&node| self.matches(network.label(node))).collect())
+    }
+}
+
+impl fmt::Display for LocationRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact(label) => write!(f, "exact:{}", label),
+            Self::Prefix(prefix) => write!(f, "prefix:{}", prefix),
+            Self::Suffix(suffix) => write!(f, "suffix:{}", suffix),
+            Self::Regex(regex) => write!(f, "regex:{}", regex),
+            Self::Set(labels) => {
+                let mut labels: Vec<&str> = labels.iter().map(|label| label.as_str()).collect();
+                labels.sort_unstable();
+                write!(f, "set:{}", labels.join(","))
+            },
+        }
+    }
+}
+
+/// Starts a ghost on every location matching `start` and counts the steps until all of them
+/// stand on a location matching `goal` at once.
+pub fn steps(network: &Network, start: &LocationRule, goal: &LocationRule) -> Result<u64, ProcessError> {
+    let starts = start.locations(network)?;
+    if let LocationRule::Exact(label) = goal {
+        network.id(label)?;
+    }
+
+    let ghosts: Vec<GhostCycle> = starts
+        .iter()
+        .map(|&start| GhostCycle::find(network, start, |location| goal.matches(location)))
+        .collect();
+
+    match &ghosts[..] {
+        [] => Err(ProcessError::NoStartLocations),
+        [ghost] => ghost.first_hit().ok_or(ProcessError::UnreachableTarget {
+            start: network.label(ghost.start).to_string(),
+            target: goal.to_string(),
+        }),
+        _ => ghosts::first_common_step(&ghosts),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[rstest]
+    #[case("suffix:A", "suffix:Z", 6)]
+    #[case("exact:11A", "suffix:Z", 2)]
+    #[case("exact:22A", "exact:22Z", 3)]
+    #[case("prefix:11A", "set:22Z,11Z", 2)]
+    #[case("regex:^(11|22)A$", "regex:Z$", 6)]
+    #[case("set:11A", "set:11B", 1)]
+    fn test_steps(#[case] start: &str, #[case] goal: &str, #[case] expected: u64) -> Result<(), ProcessError> {
+        let network = Network::parse(EXAMPLE)?;
+        assert_eq!(steps(&network, &LocationRule::parse(start)?, &LocationRule::parse(goal)?)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_steps_errors() -> Result<(), ProcessError> {
+        let network = Network::parse(EXAMPLE)?;
+        let rule = |rule_text| LocationRule::parse(rule_text);
+
+        assert!(matches!(
+            steps(&network, &rule("exact:11A")?, &rule("exact:22Z")?),
+            Err(ProcessError::UnreachableTarget { start, target }) if start == "11A" && target == "exact:22Z"
+        ));
+        assert!(matches!(steps(&network, &rule("suffix:Q")?, &rule("suffix:Z")?), Err(ProcessError::NoStartLocations)));
+        assert!(matches!(steps(&network, &rule("exact:AAA")?, &rule("suffix:Z")?), Err(ProcessError::UnknownLocation(_))));
+        assert!(matches!(rule("ends:Z"), Err(ProcessError::InvalidRule(_))));
+        assert!(matches!(rule("regex:("), Err(ProcessError::RegexError(_))));
+        Ok(())
+    }
+}
\ No newline at end of file
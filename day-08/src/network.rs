@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ProcessError {
+    #[error("no left/right instructions included in the input")]
+    MissingInstructions,
+
+    #[error("no locations were included in the input")]
+    MissingLocations,
+
+    #[error("invalid instruction `{0}`, expected `L` or `R`")]
+    InvalidInstruction(char),
+
+    #[error("invalid location line: `{0}`")]
+    InvalidLocation(String),
+
+    #[error("location `{0}` is not present in the map")]
+    UnknownLocation(String),
+
+    #[error("location `{0}` is listed more than once")]
+    DuplicateLocation(String),
+
+    #[error("the map has more locations than fit in a node id")]
+    TooManyLocations,
+
+    #[error("`{target}` is never reached when walking from `{start}`")]
+    UnreachableTarget { start: String, target: String },
+
+    #[error("invalid location rule `{0}`, expected `exact:`, `prefix:`, `suffix:`, `regex:` or `set:` followed by a pattern")]
+    InvalidRule(String),
+
+    #[error("no location matches the start rule")]
+    NoStartLocations,
+
+    #[error("there is no step at which every ghost stands on an end location")]
+    NoCommonStep,
+
+    #[error("the number of steps does not fit in a u64")]
+    StepOverflow,
+
+    #[error("invalid regex")]
+    RegexError(#[from] regex::Error),
+}
+
+/// Index of a location in a `Network`, assigned in the order the locations are listed.
+pub type NodeId = u32;
+
+/// The left/right instructions and where each location leads. Labels are interned into
+/// dense ids when parsing, left and right destinations are kept in parallel arrays and the
+/// instructions are a bit vector with a set bit for `R`, so a step is two array reads.
+#[derive(Debug)]
+pub struct Network {
+    labels: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    left: Vec<NodeId>,
+    right: Vec<NodeId>,
+    instruction_bits: Vec<u64>,
+    num_instructions: usize,
+}
+
+/// Splits `AAA = (BBB, CCC)` into its three labels.
+fn parse_location_line(location_line: &str) -> Result<(&str, &str, &str), ProcessError> {
+    let invalid = || ProcessError::InvalidLocation(location_line.to_string());
+
+    let (location, left_right_text) = location_line.split_once(" = ").ok_or_else(invalid)?;
+    let (left, right) = left_right_text
+        .trim()
+        .strip_prefix('(')
+        .and_then(|text| text.strip_suffix(')'))
+        .and_then(|text| text.split_once(", "))
+        .ok_or_else(invalid)?;
+
+    let location = location.trim();
+    if [location, left, right].iter().any(|label| label.is_empty()) {
+        return Err(invalid());
+    }
+
+    Ok((location, left, right))
+}
+
+impl Network {
+    pub fn parse(input: &str) -> Result<Self, ProcessError> {
+        let mut input_parts = input.split("\n\n");
+
+        let instructions_text = input_parts.next()
+            .ok_or(ProcessError::MissingInstructions)?
+            .trim();
+
+        let locations_text = input_parts.next()
+            .ok_or(ProcessError::MissingLocations)?;
+
+        if instructions_text.is_empty() {
+            return Err(ProcessError::MissingInstructions);
+        }
+        let mut instruction_bits = vec![0u64; instructions_text.len().div_ceil(64)];
+        for (index, instruction) in instructions_text.chars().enumerate() {
+            match instruction {
+                'L' => {},
+                'R' => instruction_bits[index / 64] |= 1 << (index % 64),
+                _ => return Err(ProcessError::InvalidInstruction(instruction)),
+            }
+        }
+
+        let location_lines = locations_text
+            .lines()
+            .filter(|location_line| !location_line.trim().is_empty())
+            .map(parse_location_line)
+            .collect::<Result<Vec<_>, ProcessError>>()?;
+
+        if location_lines.is_empty() {
+            return Err(ProcessError::MissingLocations);
+        }
+        if location_lines.len() > NodeId::MAX as usize {
+            return Err(ProcessError::TooManyLocations);
+        }
+
+        let mut labels = Vec::with_capacity(location_lines.len());
+        let mut ids = HashMap::with_capacity(location_lines.len());
+        for (location, _, _) in &location_lines {
+            if ids.insert(location.to_string(), labels.len() as NodeId).is_some() {
+                return Err(ProcessError::DuplicateLocation(location.to_string()));
+            }
+            labels.push(location.to_string());
+        }
+
+        let id_of = |label: &str| ids.get(label)
+            .copied()
+            .ok_or(ProcessError::UnknownLocation(label.to_string()));
+        let mut left = Vec::with_capacity(labels.len());
+        let mut right = Vec::with_capacity(labels.len());
+        for (_, left_label, right_label) in &location_lines {
+            left.push(id_of(left_label)?);
+            right.push(id_of(right_label)?);
+        }
+
+        Ok(Self {
+            labels,
+            ids,
+            left,
+            right,
+            instruction_bits,
+            num_instructions: instructions_text.len(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = NodeId> {
+        0..self.labels.len() as NodeId
+    }
+
+    pub fn id(&self, label: &str) -> Result<NodeId, ProcessError> {
+        self.ids.get(label)
+            .copied()
+            .ok_or(ProcessError::UnknownLocation(label.to_string()))
+    }
+
+    pub fn label(&self, node: NodeId) -> &str {
+        &self.labels[node as usize]
+    }
+
+    pub fn left(&self, node: NodeId) -> NodeId {
+        self.left[node as usize]
+    }
+
+    pub fn right(&self, node: NodeId) -> NodeId {
+        self.right[node as usize]
+    }
+
+    pub fn num_instructions(&self) -> usize {
+        self.num_instructions
+    }
+
+    /// Whether the instruction at `instruction_index` is `R`.
+    pub fn goes_right(&self, instruction_index: usize) -> bool {
+        self.instruction_bits[instruction_index / 64] >> (instruction_index % 64) & 1 == 1
+    }
+
+    /// Where following the instruction at `instruction_index` from `node` leads.
+    pub fn step(&self, node: NodeId, instruction_index: usize) -> NodeId {
+        if self.goes_right(instruction_index) {
+            self.right[node as usize]
+        } else {
+            self.left[node as usize]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), ProcessError> {
+        let network = Network::parse("\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)")?;
+        assert_eq!(network.len(), 3);
+        assert_eq!(network.num_instructions(), 3);
+        assert_eq!((0..3).map(|index| network.goes_right(index)).collect::<Vec<_>>(), [false, false, true]);
+
+        let bbb = network.id("BBB")?;
+        assert_eq!(network.label(network.step(bbb, 0)), "AAA");
+        assert_eq!(network.label(network.step(bbb, 2)), "ZZZ");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Network::parse("LR\n\nAAA = (BBB, CCC)\nBBB = (AAA, AAA)"),
+            Err(ProcessError::UnknownLocation(label)) if label == "CCC"
+        ));
+        assert!(matches!(
+            Network::parse("LX\n\nAAA = (AAA, AAA)"),
+            Err(ProcessError::InvalidInstruction('X'))
+        ));
+        assert!(matches!(
+            Network::parse("LR\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)"),
+            Err(ProcessError::DuplicateLocation(_))
+        ));
+        assert!(matches!(
+            Network::parse("LR\n\nAAA = AAA, AAA"),
+            Err(ProcessError::InvalidLocation(_))
+        ));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+use std::fs;
+use anyhow::{bail, Result};
+use day_09::diagnose::{predict, Direction, Fallback, Prediction};
+use day_09::extrapolate::parse_sequence;
+
+/// Extrapolates every line in both directions and names the lines that are not polynomial
+/// within their length. Run as `diagnose [--least-squares <degree>] [path]` to fit those
+/// lines approximately instead.
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let (fallback, path) = match &args[..] {
+        [flag, degree, rest @ ..] if flag == "--least-squares" => {
+            (Fallback::LeastSquares { degree: degree.parse()? }, rest.first())
+        },
+        [path] => (Fallback::Reject, Some(path)),
+        [] => (Fallback::Reject, None),
+        _ => bail!("usage: diagnose [--least-squares <degree>] [path]"),
+    };
+    let input = match path {
+        Some(path) => fs::read_to_string(path)?,
+        None => include_str!("../../input1.txt").to_string(),
+    };
+
+    for (line_index, line) in input.lines().enumerate() {
+        let values = parse_sequence(line)?;
+        let predictions = (
+            predict(&values, line_index + 1, Direction::Prev, fallback),
+            predict(&values, line_index + 1, Direction::Next, fallback),
+        );
+
+        match predictions {
+            (Ok(Prediction::Exact(prev)), Ok(Prediction::Exact(next))) => {
+                println!("line {}: {} .. {}", line_index + 1, prev, next);
+            },
+            (Ok(Prediction::LeastSquares(prev)), Ok(Prediction::LeastSquares(next))) => {
+                println!("line {}: ~{:.3} .. ~{:.3} (least squares)", line_index + 1, prev, next);
+            },
+            (Err(error), _) | (_, Err(error)) => println!("{}", error),
+            _ => unreachable!("both directions use the same fit"),
+        }
+    }
+
+    Ok(())
+}
\ No newline at end of file
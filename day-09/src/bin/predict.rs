@@ -0,0 +1,30 @@
+use std::fs;
+use anyhow::{bail, Context, Result};
+use itertools::Itertools;
+use day_09::extrapolate::parse_sequence;
+use day_09::polynomial::Polynomial;
+
+/// Prints, for every line, the fitted polynomial's degree and exact coefficients (constant
+/// term first) and the `k` values before and after the sequence.
+/// Run as `predict <k> [path]`.
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let (count, input) = match &args[..] {
+        [count] => (count.parse::<usize>()?, include_str!("../../input1.txt").to_string()),
+        [count, path] => (count.parse::<usize>()?, fs::read_to_string(path)?),
+        _ => bail!("usage: predict <k> [path]"),
+    };
+
+    for (line_index, line) in input.lines().enumerate() {
+        let polynomial = Polynomial::fit(&parse_sequence(line)?)
+            .with_context(|| format!("line {}", line_index + 1))?;
+
+        println!("line {}: degree {}", line_index + 1, polynomial.degree());
+        println!("  coefficients: {}", polynomial.coefficients()?.iter().join(" "));
+        println!("  previous: {}", polynomial.prev_values(count)?.iter().join(" "));
+        println!("  next: {}", polynomial.next_values(count)?.iter().join(" "));
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,180 @@
+use crate::extrapolate::SequenceError;
+use crate::polynomial::Polynomial;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Next,
+    Prev,
+}
+
+/// What to do with a line that only fits a polynomial by using every one of its values, and
+/// so never reaches an all-zero difference row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fallback {
+    Reject,
+    LeastSquares { degree: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Prediction {
+    Exact(i128),
+    LeastSquares(f64),
+}
+
+/// Extrapolates one line exactly. A line counts as polynomial when a degree below `len - 1`
+/// fits, which leaves at least one value to confirm the fit.
+pub fn predict_exact(values: &[i64], line: usize, direction: Direction) -> Result<i128, SequenceError> {
+    let polynomial = Polynomial::fit(values)?;
+
+    if polynomial.degree() + 1 < values.len() {
+        let x = match direction {
+            Direction::Next => values.len() as i128,
+            Direction::Prev => -1,
+        };
+        return polynomial.value_at(x);
+    }
+
+    if values.len() < 2 {
+        Err(SequenceError::TooShort { line, len: values.len() })
+    } else {
+        Err(SequenceError::NotPolynomial { line, len: values.len(), max_degree_tried: polynomial.degree() - 1 })
+    }
+}
+
+/// Extrapolates one line exactly when it is polynomial; otherwise the fallback decides.
+pub fn predict(values: &[i64], line: usize, direction: Direction, fallback: Fallback) -> Result<Prediction, SequenceError> {
+    match (predict_exact(values, line, direction), fallback) {
+        (Ok(value), _) => Ok(Prediction::Exact(value)),
+        (
+            Err(SequenceError::TooShort { .. } | SequenceError::NotPolynomial { .. }),
+            Fallback::LeastSquares { degree },
+        ) => {
+            let coefficients = least_squares(values, degree.min(values.len() - 1));
+            let x = match direction {
+                Direction::Next => values.len() as f64,
+                Direction::Prev => -1.0,
+            };
+            Ok(Prediction::LeastSquares(coefficients.iter().rev().fold(0.0, |value, c| value * x + c)))
+        },
+        (Err(error), _) => Err(error),
+    }
+}
+
+/// Coefficients of `1, x, x², ...` for the polynomial of the given degree closest to the
+/// values, solving the normal equations with Gaussian elimination.
+pub fn least_squares(values: &[i64], degree: usize) -> Vec<f64> {
+    let size = degree + 1;
+
+    // augmented normal equations: Σ x^(i+j) c[j] = Σ x^i y
+    let mut system = vec![vec![0.0; size + 1]; size];
+    for (x, &y) in values.iter().enumerate() {
+        let powers: Vec<f64> = (0..2 * size).map(|power| (x as f64).powi(power as i32)).collect();
+        for (i, row) in system.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().take(size).enumerate() {
+                *cell += powers[i + j];
+            }
+            row[size] += powers[i] * y as f64;
+        }
+    }
+
+    for column in 0..size {
+        let pivot = (column..size)
+            .max_by(|&a, &b| system[a][column].abs().total_cmp(&system[b][column].abs()))
+            .expect("the column has at least one row");
+        system.swap(column, pivot);
+
+        let (upper, lower) = system.split_at_mut(column + 1);
+        let pivot_row = &upper[column];
+        for row in lower {
+            let factor = row[column] / pivot_row[column];
+            for (cell, pivot_cell) in row[column..].iter_mut().zip(&pivot_row[column..]) {
+                *cell -= factor * pivot_cell;
+            }
+        }
+    }
+
+    let mut coefficients = vec![0.0; size];
+    for row in (0..size).rev() {
+        let known: f64 = (row + 1..size).map(|k| system[row][k] * coefficients[k]).sum();
+        coefficients[row] = (system[row][size] - known) / system[row][row];
+    }
+    coefficients
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extrapolate::parse_sequence;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("0 3 6 9 12 15", Direction::Next, 18)]
+    #[case("0 3 6 9 12 15", Direction::Prev, -3)]
+    #[case("1 3 6 10 15 21", Direction::Next, 28)]
+    #[case("1 3 6 10 15 21", Direction::Prev, 0)]
+    #[case("10 13 16 21 30 45", Direction::Next, 68)]
+    #[case("10 13 16 21 30 45", Direction::Prev, 5)]
+    #[case("-6 -7 -7 -6 -4 -1 3 8 14 21 29 38 48 59 71 84 98 113 129 146 164", Direction::Next, 183)]
+    #[case("-6 -7 -7 -6 -4 -1 3 8 14 21 29 38 48 59 71 84 98 113 129 146 164", Direction::Prev, -4)]
+    fn test_predict_exact(#[case] line: &str, #[case] direction: Direction, #[case] expected: i128) -> Result<(), SequenceError> {
+        let values = parse_sequence(line)?;
+        assert_eq!(predict_exact(&values, 1, direction)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_predict_matches_difference_rows() -> Result<(), SequenceError> {
+        // cubes plus a quadratic, long enough that full binomial rows would get large
+        let values: Vec<i64> = (0..40).map(|x: i64| x.pow(3) - 7 * x * x + 3).collect();
+        assert_eq!(predict_exact(&values, 1, Direction::Next)?, 40i128.pow(3) - 7 * 40 * 40 + 3);
+        assert_eq!(predict_exact(&values, 1, Direction::Prev)?, -1 - 7 + 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_not_polynomial() -> Result<(), SequenceError> {
+        let values = parse_sequence("1 2 4 8 16")?;
+        let error = predict(&values, 7, Direction::Next, Fallback::Reject).unwrap_err();
+
+        assert!(matches!(error, SequenceError::NotPolynomial { line: 7, len: 5, max_degree_tried: 3 }));
+        assert_eq!(
+            error.to_string(),
+            "line 7 is not polynomial within its 5 values: degrees 0 to 3 were tried, \
+            and none of them fits with a value left over to confirm the fit",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_too_short() -> Result<(), SequenceError> {
+        let values = parse_sequence("42")?;
+        let error = predict(&values, 3, Direction::Prev, Fallback::Reject).unwrap_err();
+
+        assert!(matches!(error, SequenceError::TooShort { line: 3, len: 1 }));
+        assert_eq!(error.to_string(), "line 3 has only 1 value, but at least 2 are needed to fit a polynomial and confirm it");
+        assert_eq!(
+            predict(&values, 3, Direction::Prev, Fallback::LeastSquares { degree: 2 })?,
+            Prediction::LeastSquares(42.0),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_least_squares_fallback() -> Result<(), SequenceError> {
+        // a line with one noisy value: the quadratic through the rest predicts 50
+        let values = parse_sequence("2 5 10 17 27 37")?;
+        let Prediction::LeastSquares(value) = predict(&values, 1, Direction::Next, Fallback::LeastSquares { degree: 2 })? else {
+            panic!("expected a least-squares prediction");
+        };
+        assert!((value - 50.0).abs() < 1.5, "predicted {}", value);
+        Ok(())
+    }
+
+    #[test]
+    fn test_least_squares_exact_fit() {
+        let coefficients = least_squares(&[3, 5, 9, 15, 23], 2);
+        for (coefficient, expected) in coefficients.iter().zip([3.0, 1.0, 1.0]) {
+            assert!((coefficient - expected).abs() < 1e-9);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SequenceError {
+    #[error("the sequence has no values")]
+    Empty,
+
+    #[error("invalid value `{0}`, expected an integer")]
+    InvalidValue(String),
+
+    #[error("a difference or extrapolated value does not fit in an i128")]
+    Overflow,
+
+    #[error("a rational number cannot have a zero denominator")]
+    ZeroDenominator,
+
+    #[error("line {line} is not polynomial within its {len} values: degrees 0 to {max_degree_tried} were tried, and none of them fits with a value left over to confirm the fit")]
+    NotPolynomial { line: usize, len: usize, max_degree_tried: usize },
+
+    #[error("line {line} has only {len} value, but at least 2 are needed to fit a polynomial and confirm it")]
+    TooShort { line: usize, len: usize },
+}
+
+pub fn parse_sequence(line: &str) -> Result<Vec<i64>, SequenceError> {
+    let values = line
+        .split_whitespace()
+        .map(|value| value.parse().map_err(|_| SequenceError::InvalidValue(value.to_string())))
+        .collect::<Result<Vec<i64>, SequenceError>>()?;
+
+    if values.is_empty() {
+        return Err(SequenceError::Empty);
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sequence() -> Result<(), SequenceError> {
+        assert_eq!(parse_sequence(" -6 7  0 ")?, [-6, 7, 0]);
+        assert!(matches!(parse_sequence(""), Err(SequenceError::Empty)));
+        assert!(matches!(parse_sequence("1 two 3"), Err(SequenceError::InvalidValue(value)) if value == "two"));
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -1,2 +1,5 @@
+pub mod diagnose;
+pub mod extrapolate;
 pub mod part1;
-pub mod part2;
\ No newline at end of file
+pub mod part2;
+pub mod polynomial;
\ No newline at end of file
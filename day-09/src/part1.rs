@@ -1,35 +1,17 @@
 use anyhow::{anyhow, Result};
+use crate::diagnose::{predict_exact, Direction};
+use crate::extrapolate::parse_sequence;
 
-fn predict_next_element(sequence: &Vec<i32>) -> Result<i32> {
-    if sequence.len() < 2 {
-        return Err(anyhow!("not enough values to compute a difference"));
-    }
-
-    let mut differences = Vec::with_capacity(sequence.len() - 1);
-    for (i, value) in sequence.iter().enumerate().skip(1) {
-        differences.push(value - sequence[i - 1]);
-    }
-
-    if differences.iter().all(|v| v == &0) {
-        Ok(sequence[0])
-    } else {
-        let next_difference = predict_next_element(&differences)?;
-        Ok(sequence.last().expect("differences is non-empty") + next_difference)
-    }
-}
-
-fn process_line(line: &str) -> Result<i32> {
-    let values: Vec<i32> = line.split(' ')
-        .map(|v| v.parse().expect("value is an integer"))
-        .collect();
-
-    predict_next_element(&values)
+fn process_line(line_number: usize, line: &str) -> Result<i128> {
+    let values = parse_sequence(line)?;
+    Ok(predict_exact(&values, line_number, Direction::Next)?)
 }
 
 pub fn process(input: &str) -> Result<String> {
-    let mut result = 0;
-    for line in input.lines() {
-        result += process_line(line)?;
+    let mut result: i128 = 0;
+    for (line_index, line) in input.lines().enumerate() {
+        let value = process_line(line_index + 1, line)?;
+        result = result.checked_add(value).ok_or(anyhow!("the sum does not fit in an i128"))?;
     }
     Ok(result.to_string())
 }
@@ -50,7 +32,13 @@ mod tests {
 
     #[test]
     fn test_process_line() -> Result<()> {
-        assert_eq!(18, process_line("0 3 6 9 12 15")?);
+        assert_eq!(18, process_line(1, "0 3 6 9 12 15")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_long_constant_line() -> Result<()> {
+        assert_eq!("5", process(&["5"; 130].join(" "))?);
         Ok(())
     }
 }
\ No newline at end of file
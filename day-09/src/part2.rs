@@ -1,35 +1,17 @@
 use anyhow::{anyhow, Result};
+use crate::diagnose::{predict_exact, Direction};
+use crate::extrapolate::parse_sequence;
 
-fn predict_prev_element(sequence: &Vec<i32>) -> Result<i32> {
-    if sequence.len() < 2 {
-        return Err(anyhow!("not enough values to compute a difference"));
-    }
-
-    let mut differences = Vec::with_capacity(sequence.len() - 1);
-    for (i, value) in sequence.iter().enumerate().skip(1) {
-        differences.push(value - sequence[i - 1]);
-    }
-
-    if differences.iter().all(|v| v == &0) {
-        Ok(sequence[0])
-    } else {
-        let prev_difference = predict_prev_element(&differences)?;
-        Ok(sequence[0] - prev_difference)
-    }
-}
-
-fn process_line(line: &str) -> Result<i32> {
-    let values: Vec<i32> = line.split(' ')
-        .map(|v| v.parse().expect("value is an integer"))
-        .collect();
-
-    predict_prev_element(&values)
+fn process_line(line_number: usize, line: &str) -> Result<i128> {
+    let values = parse_sequence(line)?;
+    Ok(predict_exact(&values, line_number, Direction::Prev)?)
 }
 
 pub fn process(input: &str) -> Result<String> {
-    let mut result = 0;
-    for line in input.lines() {
-        result += process_line(line)?;
+    let mut result: i128 = 0;
+    for (line_index, line) in input.lines().enumerate() {
+        let value = process_line(line_index + 1, line)?;
+        result = result.checked_add(value).ok_or(anyhow!("the sum does not fit in an i128"))?;
     }
     Ok(result.to_string())
 }
@@ -50,7 +32,7 @@ mod tests {
 
     #[test]
     fn test_process_line() -> Result<()> {
-        assert_eq!(5, process_line("10 13 16 21 30 45")?);
+        assert_eq!(5, process_line(1, "10 13 16 21 30 45")?);
         Ok(())
     }
 }
@@ -0,0 +1,241 @@
+use std::fmt;
+use crate::extrapolate::SequenceError;
+
+fn gcd(a: i128, b: i128) -> Result<i128, SequenceError> {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    i128::try_from(a).map_err(|_| SequenceError::Overflow)
+}
+
+/// An exact fraction kept in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub fn new(numerator: i128, denominator: i128) -> Result<Self, SequenceError> {
+        if denominator == 0 {
+            return Err(SequenceError::ZeroDenominator);
+        }
+
+        let divisor = gcd(numerator, denominator)?.max(1) * denominator.signum();
+        Ok(Self {
+            numerator: numerator.checked_div(divisor).ok_or(SequenceError::Overflow)?,
+            denominator: denominator.checked_div(divisor).ok_or(SequenceError::Overflow)?,
+        })
+    }
+
+    pub fn integer(value: i128) -> Self {
+        Self { numerator: value, denominator: 1 }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn checked_add(&self, other: &Rational) -> Result<Self, SequenceError> {
+        let divisor = gcd(self.denominator, other.denominator)?;
+        let (scale_self, scale_other) = (other.denominator / divisor, self.denominator / divisor);
+        let numerator = self.numerator
+            .checked_mul(scale_self)
+            .zip(other.numerator.checked_mul(scale_other))
+            .and_then(|(a, b)| a.checked_add(b))
+            .ok_or(SequenceError::Overflow)?;
+        let denominator = self.denominator.checked_mul(scale_self).ok_or(SequenceError::Overflow)?;
+        Self::new(numerator, denominator)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// The lowest-degree polynomial through a sequence, with the first value at `x = 0`. It is
+/// kept in Newton form, `p(x) = Σ Δᵏy[0] C(x, k)`, which stays in integers for every integer
+/// `x`; the monomial coefficients are only needed for display and are exact rationals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    forward_differences: Vec<i128>,
+    len: usize,
+}
+
+impl Polynomial {
+    pub fn fit(values: &[i64]) -> Result<Self, SequenceError> {
+        if values.is_empty() {
+            return Err(SequenceError::Empty);
+        }
+
+        // the first value of each difference row, stopping at the first all-zero row so the
+        // rows stay as small as the sequence itself
+        let mut forward_differences = Vec::new();
+        let mut row: Vec<i128> = values.iter().map(|&value| value as i128).collect();
+        while let Some(&first) = row.first() {
+            forward_differences.push(first);
+            if row.iter().all(|&difference| difference == 0) {
+                break;
+            }
+            row = row
+                .windows(2)
+                .map(|pair| pair[1].checked_sub(pair[0]).ok_or(SequenceError::Overflow))
+                .collect::<Result<Vec<i128>, SequenceError>>()?;
+        }
+
+        if forward_differences.len() > 1 && forward_differences.last() == Some(&0) {
+            forward_differences.pop();
+        }
+
+        Ok(Self { forward_differences, len: values.len() })
+    }
+
+    /// The degree, counting a constant (or all-zero) sequence as degree 0.
+    pub fn degree(&self) -> usize {
+        self.forward_differences.len() - 1
+    }
+
+    pub fn value_at(&self, x: i128) -> Result<i128, SequenceError> {
+        // C(x, k + 1) = C(x, k) (x - k) / (k + 1), which divides exactly
+        let mut binomial: i128 = 1;
+        let mut value: i128 = 0;
+        for (k, difference) in self.forward_differences.iter().enumerate() {
+            if k > 0 {
+                binomial = binomial
+                    .checked_mul(x - (k as i128 - 1))
+                    .ok_or(SequenceError::Overflow)?
+                    / k as i128;
+            }
+            value = difference
+                .checked_mul(binomial)
+                .and_then(|term| value.checked_add(term))
+                .ok_or(SequenceError::Overflow)?;
+        }
+        Ok(value)
+    }
+
+    /// The `count` values that follow the sequence.
+    pub fn next_values(&self, count: usize) -> Result<Vec<i128>, SequenceError> {
+        (0..count)
+            .map(|offset| self.value_at((self.len + offset) as i128))
+            .collect()
+    }
+
+    /// The `count` values that come before the sequence, in sequence order.
+    pub fn prev_values(&self, count: usize) -> Result<Vec<i128>, SequenceError> {
+        (1..=count)
+            .rev()
+            .map(|offset| self.value_at(-(offset as i128)))
+            .collect()
+    }
+
+    /// Coefficients of `1, x, x², ...`, expanded from the Newton form.
+    pub fn coefficients(&self) -> Result<Vec<Rational>, SequenceError> {
+        let mut coefficients = vec![Rational::integer(0); self.forward_differences.len()];
+
+        // falling factorial x (x - 1) ... (x - k + 1) in monomial form, and k!
+        let mut falling = vec![1i128];
+        let mut factorial: i128 = 1;
+        for (k, difference) in self.forward_differences.iter().enumerate() {
+            if k > 0 {
+                let mut next = vec![0i128; falling.len() + 1];
+                for (power, coefficient) in falling.iter().enumerate() {
+                    next[power + 1] = next[power + 1].checked_add(*coefficient).ok_or(SequenceError::Overflow)?;
+                    next[power] = coefficient
+                        .checked_mul(k as i128 - 1)
+                        .and_then(|scaled| next[power].checked_sub(scaled))
+                        .ok_or(SequenceError::Overflow)?;
+                }
+                falling = next;
+                factorial = factorial.checked_mul(k as i128).ok_or(SequenceError::Overflow)?;
+            }
+
+            for (power, coefficient) in falling.iter().enumerate() {
+                let numerator = difference.checked_mul(*coefficient).ok_or(SequenceError::Overflow)?;
+                coefficients[power] = coefficients[power].checked_add(&Rational::new(numerator, factorial)?)?;
+            }
+        }
+
+        Ok(coefficients)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extrapolate::parse_sequence;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("0 3 6 9 12 15", 1, "0 3", [18, 21, 24], [-9, -6, -3])]
+    #[case("1 3 6 10 15 21", 2, "1 3/2 1/2", [28, 36, 45], [1, 0, 0])]
+    #[case("10 13 16 21 30 45", 3, "10 11/3 -1 1/3", [68, 101, 146], [-19, -4, 5])]
+    #[case("4 4 4", 0, "4", [4, 4, 4], [4, 4, 4])]
+    fn test_fit(
+        #[case] line: &str,
+        #[case] degree: usize,
+        #[case] coefficients: &str,
+        #[case] next: [i128; 3],
+        #[case] prev: [i128; 3],
+    ) -> Result<(), SequenceError> {
+        let polynomial = Polynomial::fit(&parse_sequence(line)?)?;
+        assert_eq!(polynomial.degree(), degree);
+        assert_eq!(polynomial.coefficients()?.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" "), coefficients);
+        assert_eq!(polynomial.next_values(3)?, next);
+        assert_eq!(polynomial.prev_values(3)?, prev);
+        Ok(())
+    }
+
+    #[test]
+    fn test_coefficients_reproduce_values() -> Result<(), SequenceError> {
+        let values: Vec<i64> = (0..15).map(|x: i64| 3 * x.pow(5) - x.pow(4) + 11 * x - 8).collect();
+        let polynomial = Polynomial::fit(&values)?;
+        assert_eq!(polynomial.degree(), 5);
+
+        let expected = [-8, 11, 0, 0, -1, 3].map(Rational::integer);
+        assert_eq!(polynomial.coefficients()?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_long_low_degree_line() -> Result<(), SequenceError> {
+        let constant = Polynomial::fit(&[5; 130])?;
+        assert_eq!(constant.degree(), 0);
+        assert_eq!((constant.next_values(1)?, constant.prev_values(1)?), (vec![5], vec![5]));
+
+        let values: Vec<i64> = (0..300).map(|x: i64| 2 * x * x - 9 * x + 4).collect();
+        let quadratic = Polynomial::fit(&values)?;
+        assert_eq!(quadratic.degree(), 2);
+        assert_eq!((quadratic.next_values(1)?, quadratic.prev_values(1)?), (vec![2 * 300 * 300 - 9 * 300 + 4], vec![15]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() {
+        let values: Vec<i64> = (0..120).map(|i| if i % 2 == 0 { i64::MAX } else { i64::MIN }).collect();
+        assert!(matches!(Polynomial::fit(&values), Err(SequenceError::Overflow)));
+    }
+
+    #[test]
+    fn test_rational() -> Result<(), SequenceError> {
+        let half = Rational::new(2, -4)?;
+        assert_eq!((half.numerator(), half.denominator()), (-1, 2));
+        assert_eq!(half.checked_add(&Rational::new(5, 6)?)?, Rational::new(1, 3)?);
+        assert!(matches!(Rational::new(1, 0), Err(SequenceError::ZeroDenominator)));
+        assert_eq!(Rational::new(i128::MIN, 1)?.numerator(), i128::MIN);
+        assert!(matches!(Rational::new(i128::MIN, i128::MIN), Err(SequenceError::Overflow)));
+        assert!(matches!(Rational::new(i128::MIN, -1), Err(SequenceError::Overflow)));
+        Ok(())
+    }
+}
\ No newline at end of file